@@ -0,0 +1,188 @@
+use crate::board::Pos;
+use crate::geometry::Geometry;
+
+pub type Bitboard = u128;
+
+// one bit per playable square
+pub const MAX_SQUARES:usize = Bitboard::BITS as usize;
+
+// direction vectors as (row, column) steps; rows count down from the top, so
+// a negative row step moves up the board
+pub const DIAGONAL:[(i32, i32); 4] = [(-1, -1), (-1, 1), (1, -1), (1, 1)];
+pub const ORTHOGONAL:[(i32, i32); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+pub fn shift(bb:Bitboard, delta:i32) -> Bitboard {
+
+    if delta >= 0 {
+        return bb << delta;
+    }
+
+    return bb >> -delta;
+}
+
+pub fn squares(mut bb:Bitboard) -> Vec<usize> {
+
+    let mut all_squares:Vec<usize> = Vec::new();
+
+    while bb != 0 {
+
+        all_squares.push(bb.trailing_zeros() as usize);
+
+        bb &= bb - 1;
+    }
+
+    return all_squares;
+}
+
+// The playable squares are numbered row by row from the top left, so the
+// distance to a neighbour along a direction depends on where the square sits.
+// Every direction is stored as a list of (source mask, shift) pairs: shifting
+// the masked part of a bitboard moves each piece one step along that
+// direction, and squares whose neighbour would fall off the board are simply
+// left out of every mask.
+#[derive(Clone)]
+pub struct Layout {
+    pub rows:usize,
+    pub cols:usize,
+    pub playable:Bitboard,
+    pub directions:Vec<(i32, i32)>,
+
+    positions:Vec<Pos>,
+    numbers:Vec<Option<usize>>,
+    steps:Vec<Vec<(Bitboard, i32)>>
+}
+
+impl Layout {
+
+    pub fn new(geometry:&Geometry, directions:&[(i32, i32)]) -> Layout {
+
+        let rows:usize = geometry.rows;
+        let cols:usize = geometry.cols;
+
+        // more squares would wrap around the bitboard and corrupt positions
+        assert!(geometry.square_count() <= MAX_SQUARES, "a {}x{} board with {} playable squares does not fit in a {} square bitboard", rows, cols, geometry.square_count(), MAX_SQUARES);
+
+        let mut layout:Layout = Layout {
+            rows,
+            cols,
+            playable:0,
+            directions:Vec::new(),
+            positions:Vec::new(),
+            numbers:Vec::new(),
+            steps:Vec::new()
+        };
+
+        for x in 0..rows {
+            for y in 0..cols {
+
+                if geometry.is_playable(x, y) {
+                    layout.playable |= 1 << layout.positions.len();
+                    layout.numbers.push(Some(layout.positions.len()));
+                    layout.positions.push(Pos::new(x, y));
+                } else {
+                    layout.numbers.push(None);
+                }
+            }
+        }
+
+        // every direction is paired with its reverse
+        for (x_dir, y_dir) in directions.iter() {
+
+            for dir in [(*x_dir, *y_dir), (-x_dir, -y_dir)].iter() {
+
+                if !layout.directions.contains(dir) {
+                    layout.directions.push(*dir);
+                }
+            }
+        }
+
+        for d in 0..layout.directions.len() {
+
+            let (x_dir, y_dir) = layout.directions[d];
+
+            let mut groups:Vec<(Bitboard, i32)> = Vec::new();
+
+            for sq in squares(layout.playable) {
+
+                let curr:Pos = layout.pos(sq);
+
+                let next_x:i32 = curr.x as i32 + x_dir;
+                let next_y:i32 = curr.y as i32 + y_dir;
+
+                if next_x < 0 || next_y < 0 || next_x >= rows as i32 || next_y >= cols as i32 {
+                    continue;
+                }
+
+                let next:usize = match layout.square(Pos::new(next_x as usize, next_y as usize)) {
+                    Some(_sq) => _sq,
+                    None => continue
+                };
+
+                let delta:i32 = next as i32 - sq as i32;
+
+                match groups.iter_mut().find(|g| g.1 == delta) {
+                    Some(group) => group.0 |= 1 << sq,
+                    None => groups.push((1 << sq, delta))
+                }
+            }
+
+            layout.steps.push(groups);
+        }
+
+        return layout;
+    }
+
+    pub fn square(&self, pos:Pos) -> Option<usize> {
+
+        if pos.x >= self.rows || pos.y >= self.cols {
+            return None;
+        }
+
+        return self.numbers[pos.x * self.cols + pos.y];
+    }
+
+    pub fn pos(&self, sq:usize) -> Pos {
+        return self.positions[sq];
+    }
+
+    pub fn bit(&self, pos:Pos) -> Bitboard {
+
+        match self.square(pos) {
+            Some(sq) => 1 << sq,
+            None => 0
+        }
+    }
+
+    pub fn row_mask(&self, row:usize) -> Bitboard {
+
+        let mut mask:Bitboard = 0;
+
+        for y in 0..self.cols {
+            mask |= self.bit(Pos::new(row, y));
+        }
+
+        return mask;
+    }
+
+    pub fn direction(&self, vector:(i32, i32)) -> Option<usize> {
+        return self.directions.iter().position(|d| *d == vector);
+    }
+
+    pub fn reverse(&self, dir:usize) -> usize {
+
+        let (x_dir, y_dir) = self.directions[dir];
+
+        return self.direction((-x_dir, -y_dir)).unwrap_or(dir);
+    }
+
+    pub fn step(&self, bb:Bitboard, dir:usize) -> Bitboard {
+
+        let mut next:Bitboard = 0;
+
+        for (mask, delta) in self.steps[dir].iter() {
+            next |= shift(bb & mask, *delta);
+        }
+
+        return next;
+    }
+}
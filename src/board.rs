@@ -3,695 +3,789 @@ use std::cmp;
 use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
 use std::collections::hash_map::DefaultHasher;
+use std::sync::atomic::AtomicBool;
 
-#[derive(Debug, Copy, Clone)]
-pub struct Piece {
-    pub x:usize,
-    pub y:usize
-}
+use crate::bitboard::{self, Bitboard, Layout};
+use crate::error::SetupError;
+use crate::moves::Move;
+use crate::piece::{Color, Piece, PieceKind};
+use crate::result::{DrawReason, GameResult};
+use crate::rules::{CaptureRule, MajorityRule, PromotionRule, Rules};
+use crate::search::{SearchInfo, TimeControl};
 
-impl Piece {
+pub type CacheTable = HashMap<String, Vec<usize>>;
 
-    pub fn new(x_pos:usize, y_pos:usize) -> Piece {
+pub const SEARCH_DEPTH:i32 = 6;
 
-        Piece {
-            x:x_pos,
-            y:y_pos
-        }
-    }
-}
+pub(crate) const WIN_SCORE:i32 = 100000;
+
+// pieces taken (or their value), whether a king captures, kings taken, and
+// which of the captured pieces were kings in order; compared left to right
+type CaptureRank = (usize, bool, usize, Vec<bool>);
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct Pos {
     pub x:usize,
-    pub y:usize,
-    pub piece:Option<Piece>
+    pub y:usize
 }
 
 impl Pos {
 
-    pub fn new(x_pos:usize, y_pos:usize, curr_piece:Option<Piece>) -> Pos {
+    pub fn new(x_pos:usize, y_pos:usize) -> Pos {
 
         Pos {
             x:x_pos,
-            y:y_pos,
-            piece:curr_piece
+            y:y_pos
         }
     }
 }
 
+#[derive(Clone)]
+struct Undo {
+    mv:Move,
+    men:[Bitboard; 2],
+    kings:[Bitboard; 2],
+    curr_player:Color,
+    no_progress:usize
+}
+
+#[derive(Clone)]
 pub struct Board {
 
-    pub row:usize,
-    pub col:usize,
+    // the size, rules and the move tables below are fixed by with_rules
+    pub(crate) row:usize,
+    pub(crate) col:usize,
+
+    // op_side is the color the engine plays, my_side its opponent
+    pub my_side:Color,
+    pub op_side:Color,
 
-    pub my_side:i32,
-    pub op_side:i32,
+    pub curr_player:Color,
 
-    pub curr_player:i32,
+    pub(crate) rules:Rules,
 
-    pub state:Vec<Vec<i32>>,
+    pub men:[Bitboard; 2],
+    pub kings:[Bitboard; 2],
 
-    pub my_pieces:Vec<Pos>,
-    pub op_pieces:Vec<Pos>,
+    pub cache_table:CacheTable,
 
-    pub cache_table:HashMap<String, Vec<usize>>,
-    pub minax_cache:HashMap<String, i32>,
+    // how long ultimate_move thinks, and what its last search reached
+    pub time_control:TimeControl,
+    pub last_search:Option<SearchInfo>,
 
-    pub quix:usize
+    // plies since the last capture or man move
+    pub no_progress:usize,
+
+    layout:Layout,
+    history:Vec<Undo>,
+
+    // direction indexes into the layout, per color for men
+    man_moves:[Vec<usize>; 2],
+    man_captures:[Vec<usize>; 2],
+    king_moves:Vec<usize>,
+    king_captures:Vec<usize>
+}
+
+impl Default for Board {
+
+    fn default() -> Board {
+        return Board::new();
+    }
 }
 
 impl Board {
 
     pub fn new() -> Board {
+        return Board::with_rules(Rules::default());
+    }
+
+    pub fn with_rules(rules:Rules) -> Board {
+
+        let layout:Layout = Layout::new(&rules.geometry, &[rules.directions.clone(), rules.capture_directions.clone()].concat());
+
+        let man_moves:[Vec<usize>; 2] = [
+            Board::direction_indexes(&layout, &rules.directions, Color::White, false),
+            Board::direction_indexes(&layout, &rules.directions, Color::Black, false)
+        ];
+
+        let man_captures:[Vec<usize>; 2] = [
+            Board::direction_indexes(&layout, &rules.capture_directions, Color::White, rules.men_capture_backward),
+            Board::direction_indexes(&layout, &rules.capture_directions, Color::Black, rules.men_capture_backward)
+        ];
+
+        let king_moves:Vec<usize> = Board::direction_indexes(&layout, &rules.directions, Color::White, true);
+        let king_captures:Vec<usize> = Board::direction_indexes(&layout, &rules.capture_directions, Color::White, true);
 
         Board {
-            row:8,
-            col:8,
+            row:rules.geometry.rows,
+            col:rules.geometry.cols,
 
-            my_side:1,
-            op_side:-1,
+            my_side:Color::White,
+            op_side:Color::Black,
 
-            curr_player:1,
+            curr_player:Color::White,
 
-            state:Vec::new(),
+            rules,
 
-            my_pieces:Vec::new(),
-            op_pieces:Vec::new(),
+            men:[0, 0],
+            kings:[0, 0],
 
             cache_table:HashMap::new(),
-            minax_cache:HashMap::new(),
 
-            quix:0
+            time_control:TimeControl::Depth(SEARCH_DEPTH),
+            last_search:None,
+
+            no_progress:0,
+
+            layout,
+            history:Vec::new(),
+
+            man_moves,
+            man_captures,
+            king_moves,
+            king_captures
         }
     }
 
-    pub fn init(&mut self) {
+    // rules give directions as seen from White, so Black's are flipped
+    // top to bottom
+    fn direction_indexes(layout:&Layout, vectors:&[(i32, i32)], side:Color, backward:bool) -> Vec<usize> {
 
-        for i in 0..self.row {
+        let mut dirs:Vec<usize> = Vec::new();
 
-            let mut row:Vec<i32> = Vec::new();
+        for (x_dir, y_dir) in vectors.iter() {
 
-            for j in 0..self.col {
+            if *x_dir > 0 && !backward {
+                continue;
+            }
 
-                let even:bool = (i % 2 == 0) && (j % 2 == 0);
-                let odd:bool = (i % 2 != 0) && (j % 2 != 0);
+            let vector:(i32, i32) = if side == Color::White { (*x_dir, *y_dir) } else { (-x_dir, *y_dir) };
 
-                if even || odd || i == 3 || i == 4 {
-                    row.push(0);
-                } else {
+            if let Some(dir) = layout.direction(vector) {
+                dirs.push(dir);
+            }
+        }
 
-                    let mut _piece:Pos = Pos::new(i, j, None);
+        return dirs;
+    }
 
-                    let x:i32 = i as i32;
+    pub fn init(&mut self) {
 
-                    if 0 <= x && x <= 2 {
-                        row.push(self.op_side);
-                        self.op_pieces.push(_piece);
-                    } else {
-                        row.push(self.my_side);
-                        self.my_pieces.push(_piece);
-                    }
-                }
-            }
+        self.men = [0, 0];
+        self.kings = [0, 0];
 
-            self.state.push(row);
+        let start_rows:Vec<usize> = self.rules.geometry.start_rows.clone();
+
+        for i in 0..start_rows.len() {
+            self.men[Color::Black.index()] |= self.own_row_mask(Color::Black, start_rows[i]);
+            self.men[Color::White.index()] |= self.own_row_mask(Color::White, start_rows[i]);
         }
     }
 
-    fn clone_board(&mut self) -> Board {
-        
-        let mut _board:Board = Board::new();
-
-        _board.init();
+    fn pieces(&self, color:Color) -> Bitboard {
 
-        _board.state = self.state.clone();
-        _board.curr_player = self.curr_player;
+        let idx:usize = color.index();
 
-        _board.my_pieces = self.my_pieces.clone();
-        _board.op_pieces = self.op_pieces.clone();
+        return self.men[idx] | self.kings[idx];
+    }
 
-        return _board;
+    fn empty(&self) -> Bitboard {
+        return self.layout.playable & !(self.pieces(Color::White) | self.pieces(Color::Black));
     }
 
-    pub fn do_move(&mut self, mut piece:Pos, next_pos:Pos) {
+    pub fn piece_at(&self, pos:Pos) -> Option<Piece> {
 
-        let checkas_len:i32;
+        let bit:Bitboard = self.layout.bit(pos);
 
-        if self.state[piece.x][piece.y] == self.my_side {
-            checkas_len = self.my_pieces.len() as i32;
-        } else {
-            checkas_len = self.op_pieces.len() as i32;
+        for color in [Color::White, Color::Black].iter() {
+
+            let idx:usize = color.index();
+
+            if self.men[idx] & bit != 0 {
+                return Some(Piece::new(*color, PieceKind::Man));
+            } else if self.kings[idx] & bit != 0 {
+                return Some(Piece::new(*color, PieceKind::King));
+            }
         }
 
-        let (legal, took) = self.is_move_legal(piece, next_pos);
+        return None;
+    }
 
-        if legal {
+    pub fn rules(&self) -> &Rules {
+        return &self.rules;
+    }
 
-            let checkas_new_len:i32;
+    pub fn rows(&self) -> usize {
+        return self.row;
+    }
 
-            if self.state[piece.x][piece.y] == self.my_side {
-                checkas_new_len = self.my_pieces.len() as i32;
-            } else {
-                checkas_new_len = self.op_pieces.len() as i32;
-            }
+    pub fn cols(&self) -> usize {
+        return self.col;
+    }
 
-            if checkas_len == checkas_new_len {
-                self.scan_board(piece);
-            }
+    pub fn square_count(&self) -> usize {
+        return self.layout.playable.count_ones() as usize;
+    }
 
-            if next_pos.x == 0 && self.state[piece.x][piece.y] == self.my_side {
-                self.state[next_pos.x][next_pos.y] = 2;
-            } else if next_pos.x == 7 && self.state[piece.x][piece.y] == self.op_side {
-                self.state[next_pos.x][next_pos.y] = -2;
-            } else {
-                self.state[next_pos.x][next_pos.y] = self.state[piece.x][piece.y];
-            }
+    // squares are numbered from 1 along the rows from the top left, the
+    // standard numbering for English and International draughts
+    pub fn square_number(&self, pos:Pos) -> Option<usize> {
+        return self.layout.square(pos).map(|sq| sq + 1);
+    }
 
-            self.state[piece.x][piece.y] = 0;
+    pub fn square_pos(&self, number:usize) -> Option<Pos> {
 
-            if took {
+        if number == 0 || number > self.square_count() {
+            return None;
+        }
 
-                let poss_moves:Vec<Pos> = self.possible_moves(next_pos, None);
+        return Some(self.layout.pos(number - 1));
+    }
 
-                let mut new_pos:Pos = Pos::new(0, 0, None);
+    pub fn to_state(&self) -> Vec<Vec<i32>> {
 
-                let mut found:bool = false;
+        let mut state:Vec<Vec<i32>> = Vec::new();
 
-                for i in 0..poss_moves.len() {
+        for i in 0..self.row {
 
-                    if let Some(p) = poss_moves[i].piece {
+            let mut row:Vec<i32> = Vec::new();
 
-                        found = true;
+            for j in 0..self.col {
+                row.push(self.piece_at(Pos::new(i, j)).map_or(0, |p| p.value()));
+            }
 
-                        piece = next_pos;
+            state.push(row);
+        }
 
-                        new_pos = Pos::new(p.x, p.y, None);
+        return state;
+    }
 
-                        break;
-                    }
-                }
+    pub fn from_state(&mut self, state:&[Vec<i32>]) {
+
+        self.men = [0, 0];
+        self.kings = [0, 0];
+
+        for i in 0..state.len() {
+            for j in 0..state[i].len() {
+
+                let bit:Bitboard = self.layout.bit(Pos::new(i, j));
 
-                if found {
-                    self.do_move(piece, new_pos);
+                let piece:Piece = match Piece::from_value(state[i][j]) {
+                    Some(_p) if bit != 0 => _p,
+                    _ => continue
+                };
+
+                let idx:usize = piece.color.index();
+
+                if piece.is_king() {
+                    self.kings[idx] |= bit;
+                } else {
+                    self.men[idx] |= bit;
                 }
             }
         }
 
-        self.curr_player = -self.curr_player;
+        self.history.clear();
+        self.no_progress = 0;
     }
 
-    fn take_piece(&mut self, x_pos:usize, y_pos:usize) {
-        self.state[x_pos][y_pos] = 0;
+    pub fn clear(&mut self) {
+
+        self.men = [0, 0];
+        self.kings = [0, 0];
+
+        self.history.clear();
+        self.no_progress = 0;
     }
 
-    fn is_move_legal(&mut self, piece:Pos, next_pos:Pos) -> (bool, bool) {
+    pub fn place(&mut self, pos:Pos, piece:Piece) -> Result<(), SetupError> {
 
-        let mut legal:bool = false;
-        let mut took_piece:bool = false;
+        self.put(pos, piece)?;
 
-        let poss_moves:Vec<Pos> = self.possible_moves(piece, None);
+        self.history.clear();
+        self.no_progress = 0;
 
-        if poss_moves.len() > 0 {
+        return Ok(());
+    }
 
-            for i in 0..poss_moves.len() {
+    fn put(&mut self, pos:Pos, piece:Piece) -> Result<(), SetupError> {
 
-                let x_pos:usize = poss_moves[i].x;
-                let y_pos:usize = poss_moves[i].y;
+        if pos.x >= self.row || pos.y >= self.col {
+            return Err(SetupError::OffBoard(pos));
+        }
 
-                if x_pos == next_pos.x && y_pos == next_pos.y {
+        let bit:Bitboard = self.layout.bit(pos);
 
-                    if let Some(p) = poss_moves[i].piece {
+        if bit == 0 {
+            return Err(SetupError::NotPlayable(pos));
+        }
 
-                        self.take_piece(p.x, p.y);
-                        took_piece = true;
-                    }
+        let square:usize = self.square_number(pos).unwrap_or(0);
 
-                    legal = true;
-                    break;
-                }
-            }
+        if bit & !self.empty() != 0 {
+            return Err(SetupError::Occupied(square));
         }
 
-        return (legal, took_piece);
-    }
+        if !piece.is_king() && bit & self.promotion_row(piece.color) != 0 {
+            return Err(SetupError::ManOnPromotionRow(square));
+        }
 
-    fn scan_board(&mut self, piece:Pos) {
+        if piece.is_king() {
+            self.kings[piece.color.index()] |= bit;
+        } else {
+            self.men[piece.color.index()] |= bit;
+        }
 
-        let all_pieces:Vec<Pos> = self.all_movable_pieces(self.curr_player);
+        return Ok(());
+    }
 
-        let mut found:bool = false;
+    // replaces the whole position; on an error the board is left as it was
+    pub fn setup(&mut self, pieces:&[(Pos, Piece)], to_move:Color) -> Result<(), SetupError> {
 
-        for i in 0..all_pieces.len() {
+        let men:[Bitboard; 2] = self.men;
+        let kings:[Bitboard; 2] = self.kings;
 
-            if found {
-                break;
-            }
+        self.men = [0, 0];
+        self.kings = [0, 0];
 
-            if all_pieces[i].x != piece.x && all_pieces[i].y != piece.y {
+        for i in 0..pieces.len() {
 
-                let curr_piece:Pos = Pos::new(all_pieces[i].x, all_pieces[i].y, None);
+            if let Err(e) = self.put(pieces[i].0, pieces[i].1) {
 
-                let moves:Vec<Pos> = self.possible_moves(curr_piece, None);
+                self.men = men;
+                self.kings = kings;
 
-                for j in 0..moves.len() {
+                return Err(e);
+            }
+        }
 
-                    if let Some(_p) = moves[j].piece {
+        self.history.clear();
+        self.no_progress = 0;
+        self.curr_player = to_move;
 
-                        found = true;
+        return Ok(());
+    }
 
-                        self.take_piece(all_pieces[i].x, all_pieces[i].y);
+    pub fn do_move(&mut self, mv:&Move) -> bool {
 
-                        break;
-                    }
-                }
-            }
+        let legal_moves:Vec<Move> = self.legal_moves();
+
+        if !legal_moves.contains(mv) {
+            return false;
         }
+
+        self.make_move(mv);
+
+        return true;
     }
 
-    fn check_all_pieces(&mut self) {
+    pub fn make_move(&mut self, mv:&Move) {
 
-        self.op_pieces = Vec::new();
-        self.my_pieces = Vec::new();
+        self.history.push(Undo {
+            mv:mv.clone(),
+            men:self.men,
+            kings:self.kings,
+            curr_player:self.curr_player,
+            no_progress:self.no_progress
+        });
 
-        for i in 0..self.row {
-            for j in 0..self.col {
+        let piece:Piece = match self.piece_at(mv.from) {
+            Some(_p) => _p,
+            None => return
+        };
 
-                if self.state[i][j] >= self.my_side {
+        let side:Color = piece.color;
 
-                    let _piece:Pos = Pos::new(i, j, None);
-                    self.my_pieces.push(_piece);
+        let idx:usize = side.index();
+        let op_idx:usize = side.opposite().index();
 
-                } else if self.state[i][j] <= self.op_side {
+        let mut huffed:Bitboard = 0;
 
-                    let _piece:Pos = Pos::new(i, j, None);
-                    self.op_pieces.push(_piece);
-                }
-            }
+        if self.rules.capture_rule == CaptureRule::Huff && !mv.is_capture() {
+            huffed = self.jumpers(side);
         }
-    }
 
-    pub fn all_movable_pieces(&mut self, side:i32) -> Vec<Pos> {
+        let from_bit:Bitboard = self.layout.bit(mv.from);
 
-        self.check_all_pieces();
+        self.men[idx] &= !from_bit;
+        self.kings[idx] &= !from_bit;
 
-        let mut all_pieces:Vec<Pos> = Vec::new();
+        for i in 0..mv.captured.len() {
 
-        if side == self.my_side {
+            let bit:Bitboard = self.layout.bit(mv.captured[i]);
 
-            for i in 0..self.my_pieces.len() {
+            self.men[op_idx] &= !bit;
+            self.kings[op_idx] &= !bit;
+        }
 
-                let poss_moves:Vec<Pos> = self.possible_moves(self.my_pieces[i], None);
+        let to_bit:Bitboard = self.layout.bit(mv.to());
 
-                if poss_moves.len() > 0 {
-                    all_pieces.push(self.my_pieces[i]);
-                }
-            }
+        let mut crowned:bool = to_bit & self.promotion_row(side) != 0;
 
+        if self.rules.promotion == PromotionRule::ContinuesAsKing {
+            crowned = mv.path.iter().any(|p| self.layout.bit(*p) & self.promotion_row(side) != 0);
+        }
+
+        if piece.is_king() || crowned {
+            self.kings[idx] |= to_bit;
         } else {
+            self.men[idx] |= to_bit;
+        }
 
-            for i in 0..self.op_pieces.len() {
+        if huffed != 0 {
 
-                let poss_moves:Vec<Pos> = self.possible_moves(self.op_pieces[i], None);
+            // a piece that could have captured is huffed; if only the moved
+            // piece had a capture, it is removed from the square it moved to
+            let others:Bitboard = huffed & !from_bit;
 
-                if poss_moves.len() > 0 {
-                    all_pieces.push(self.op_pieces[i]);
-                }
-            }
-        }
+            let huff_bit:Bitboard = if others != 0 { others & others.wrapping_neg() } else { to_bit };
 
-        return all_pieces;
-    }
+            self.men[idx] &= !huff_bit;
+            self.kings[idx] &= !huff_bit;
+        }
 
-    fn check_piece_history(&mut self, piece:Pos, history:&Vec<(usize, usize)>) -> bool {
+        if mv.is_capture() || !piece.is_king() || huffed != 0 {
+            self.no_progress = 0;
+        } else {
+            self.no_progress += 1;
+        }
 
-        let mut been:bool = false;
+        self.curr_player = self.curr_player.opposite();
+    }
 
-        for i in 0..history.len() {
+    pub fn unmake_move(&mut self) -> Option<Move> {
 
-            let x_pos = history[i].0;
-            let y_pos = history[i].1;
+        let undo:Undo = self.history.pop()?;
 
-            if x_pos == piece.x && piece.y == y_pos {
-                been = true;
-                break;
-            }
-        }
+        self.men = undo.men;
+        self.kings = undo.kings;
+        self.curr_player = undo.curr_player;
+        self.no_progress = undo.no_progress;
 
-        return been;
+        return Some(undo.mv);
     }
 
-    pub fn possible_moves(&mut self, piece:Pos, prev_pos:Option<&Vec<(usize, usize)>>) -> Vec<Pos> {
+    fn man_directions(&self, side:Color) -> Vec<usize> {
+        return self.man_moves[side.index()].clone();
+    }
 
-        let mut moves:Vec<Pos> = Vec::new();
+    fn capture_directions(&self, side:Color, is_king:bool) -> Vec<usize> {
 
-        let x_pos = piece.x;
-        let y_pos = piece.y;
+        if is_king {
+            return self.king_captures.clone();
+        }
 
-        let mut empty:bool = true;
+        return self.man_captures[side.index()].clone();
+    }
 
-        let mut h:Vec<(usize, usize)> = Vec::new();
+    fn jumpers(&self, side:Color) -> Bitboard {
 
-        if let Some(history) = prev_pos {
-            empty = false;
-            h = history.to_vec();
-        }
+        let idx:usize = side.index();
 
-        if self.check_upper_left(x_pos, y_pos).1 {
+        let opponent:Bitboard = self.pieces(side.opposite());
+        let empty:Bitboard = self.empty();
 
-            if !empty {
+        let mut jumpers:Bitboard = 0;
 
-                let new_pos:Pos = Pos::new(x_pos - 2, y_pos - 2, None);
+        for dir in self.capture_directions(side, true) {
 
-                if !self.check_piece_history(new_pos, &h) {
-                    if self.state[x_pos - 2][y_pos - 2] == 0 && self.state[x_pos - 1][y_pos - 1] != 0 && self.state[x_pos - 1][y_pos - 1] != self.state[x_pos][y_pos] {
+            let back:usize = self.layout.reverse(dir);
 
-                        let mut _x = (x_pos - 1) as i32;
-                        let mut _y = (y_pos - 1) as i32;
+            // a flying king may stand any number of empty squares behind the
+            // piece it captures
+            let mut ray:Bitboard = self.layout.step(self.layout.step(empty, back) & opponent, back);
 
-                        _x = _x.abs();
-                        _y = _y.abs();
+            while ray != 0 {
 
-                        let take_piece:Piece = Piece::new(_x as usize, _y as usize);
+                jumpers |= ray & self.kings[idx];
 
-                        _x = (x_pos - 2) as i32;
-                        _y = (y_pos - 2) as i32;
+                if !self.rules.flying_kings {
+                    break;
+                }
 
-                        _x = _x.abs();
-                        _y = _y.abs();
+                ray = self.layout.step(ray & empty, back);
+            }
+        }
 
-                        let poss_move:Pos = Pos::new(_x as usize, _y as usize, Some(take_piece));
+        let men_targets:Bitboard = self.men_targets(side, opponent);
 
-                        moves.push(poss_move);
-                        h.push((x_pos, y_pos));
+        for dir in self.capture_directions(side, false) {
 
-                        let new_moves:Vec<Pos> = self.possible_moves(poss_move, Some(&h));
+            let back:usize = self.layout.reverse(dir);
 
-                        if new_moves.len() > 0 {
-                            for i in 0..new_moves.len() {
-                                moves.push(new_moves[i]);
-                            }
-                        }
-                    }
-                }
-            } else {
+            jumpers |= self.layout.step(self.layout.step(empty, back) & men_targets, back) & self.men[idx];
+        }
 
-                if self.state[x_pos - 2][y_pos - 2] == 0 && self.state[x_pos - 1][y_pos - 1] != 0 && self.state[x_pos - 1][y_pos - 1] != self.state[x_pos][y_pos] {
-                    
-                    let mut _x = (x_pos - 1) as i32;
-                        let mut _y = (y_pos - 1) as i32;
+        return jumpers;
+    }
 
-                        _x = _x.abs();
-                        _y = _y.abs();
+    // the opponent pieces a man is allowed to jump
+    fn men_targets(&self, side:Color, opponent:Bitboard) -> Bitboard {
 
-                        let take_piece:Piece = Piece::new(_x as usize, _y as usize);
+        if self.rules.men_capture_kings {
+            return opponent;
+        }
 
-                        _x = (x_pos - 2) as i32;
-                        _y = (y_pos - 2) as i32;
+        return opponent & !self.kings[side.opposite().index()];
+    }
 
-                        _x = _x.abs();
-                        _y = _y.abs();
+    fn movers(&self, side:Color) -> Bitboard {
 
-                        let poss_move:Pos = Pos::new(_x as usize, _y as usize, Some(take_piece));
+        let jumpers:Bitboard = self.jumpers(side);
 
-                    moves.push(poss_move);
+        if jumpers != 0 && self.rules.capture_rule == CaptureRule::Mandatory {
+            return jumpers;
+        }
 
-                    let mut his:Vec<(usize, usize)> = Vec::new();
+        let idx:usize = side.index();
 
-                    his.push((x_pos, y_pos));
+        let men:Bitboard = self.men[idx];
+        let empty:Bitboard = self.empty();
 
-                    let new_moves:Vec<Pos> = self.possible_moves(poss_move, Some(&his));
+        let mut movers:Bitboard = jumpers;
 
-                    if new_moves.len() > 0 {
-                        for i in 0..new_moves.len() {
-                            moves.push(new_moves[i]);
-                        }
-                    }
-                }
-            }
+        for dir in self.man_directions(side) {
+            movers |= self.layout.step(empty, self.layout.reverse(dir)) & men;
         }
 
-        if self.check_upper_right(x_pos, y_pos).1 {
+        for i in 0..self.king_moves.len() {
+            movers |= self.layout.step(empty, self.layout.reverse(self.king_moves[i])) & self.kings[idx];
+        }
 
-            if !empty {
+        return movers;
+    }
 
-                let new_pos:Pos = Pos::new(x_pos - 2, y_pos + 2, None);
+    pub fn all_movable_pieces(&mut self, side:Color) -> Vec<Pos> {
 
-                if !self.check_piece_history(new_pos, &h) {
-                    if self.state[x_pos - 2][y_pos + 2] == 0 && self.state[x_pos - 1][y_pos + 1] != 0 && self.state[x_pos - 1][y_pos + 1] != self.state[x_pos][y_pos] {
+        let movers:Bitboard = self.movers(side);
 
-                        let mut _x = (x_pos - 1) as i32;
-                        let mut _y = (y_pos + 1) as i32;
+        let mut all_pieces:Vec<Pos> = bitboard::squares(movers).iter().map(|sq| self.layout.pos(*sq)).collect();
 
-                        _x = _x.abs();
-                        _y = _y.abs();
+        if self.majority_applies(side) {
 
-                        let take_piece:Piece = Piece::new(_x as usize, _y as usize);
+            let best:CaptureRank = self.best_capture(side);
 
-                        _x = (x_pos - 2) as i32;
-                        _y = (y_pos + 2) as i32;
+            all_pieces.retain(|p| self.piece_moves(*p).iter().any(|m| self.capture_rank(m) == best));
+        }
 
-                        _x = _x.abs();
-                        _y = _y.abs();
+        return all_pieces;
+    }
 
-                        let poss_move:Pos = Pos::new(_x as usize, _y as usize, Some(take_piece));
+    pub fn legal_moves(&mut self) -> Vec<Move> {
 
-                        moves.push(poss_move);
-                        h.push((x_pos, y_pos));
+        let mut moves:Vec<Move> = Vec::new();
 
-                        let new_moves:Vec<Pos> = self.possible_moves(poss_move, Some(&h));
+        for sq in bitboard::squares(self.movers(self.curr_player)) {
+            moves.extend(self.piece_moves(self.layout.pos(sq)));
+        }
 
-                        if new_moves.len() > 0 {
-                            for i in 0..new_moves.len() {
-                                moves.push(new_moves[i]);
-                            }
-                        }
-                    }
-                }
-            } else {
+        if self.majority_applies(self.curr_player) {
 
-                if self.state[x_pos - 2][y_pos + 2] == 0 && self.state[x_pos - 1][y_pos + 1] != 0 && self.state[x_pos - 1][y_pos + 1] != self.state[x_pos][y_pos] {
+            let best:CaptureRank = moves.iter().map(|m| self.capture_rank(m)).max().unwrap_or_default();
 
-                    let mut _x = (x_pos - 1) as i32;
-                    let mut _y = (y_pos + 1) as i32;
+            moves.retain(|m| self.capture_rank(m) == best);
+        }
 
-                    _x = _x.abs();
-                    _y = _y.abs();
+        return moves;
+    }
 
-                    let take_piece:Piece = Piece::new(_x as usize, _y as usize);
+    fn majority_applies(&self, side:Color) -> bool {
+        return self.rules.majority != MajorityRule::FreeChoice && self.rules.capture_rule == CaptureRule::Mandatory && self.jumpers(side) != 0;
+    }
 
-                    _x = (x_pos - 2) as i32;
-                    _y = (y_pos + 2) as i32;
+    fn capture_rank(&self, mv:&Move) -> CaptureRank {
 
-                    _x = _x.abs();
-                    _y = _y.abs();
+        let is_king:bool = self.piece_at(mv.from).is_some_and(|p| p.is_king());
 
-                    let poss_move:Pos = Pos::new(_x as usize, _y as usize, Some(take_piece));
+        let kings_taken:Vec<bool> = mv.captured.iter().map(|p| self.piece_at(*p).is_some_and(|q| q.is_king())).collect();
 
-                    moves.push(poss_move);
+        match self.rules.majority {
+            MajorityRule::FreeChoice => (0, false, 0, Vec::new()),
+            MajorityRule::MostPieces => (mv.captured.len(), false, 0, Vec::new()),
+            MajorityRule::Italian => (mv.captured.len(), is_king, kings_taken.iter().filter(|k| **k).count(), kings_taken),
+            MajorityRule::Frisian => {
+                let kings:usize = kings_taken.iter().filter(|k| **k).count();
+                (100 * (mv.captured.len() - kings) + 199 * kings, is_king, 0, Vec::new())
+            }
+        }
+    }
 
-                    let mut his:Vec<(usize, usize)> = Vec::new();
+    fn best_capture(&self, side:Color) -> CaptureRank {
 
-                    his.push((x_pos, y_pos));
+        let mut best:CaptureRank = CaptureRank::default();
 
-                    let new_moves:Vec<Pos> = self.possible_moves(poss_move, Some(&his));
+        for sq in bitboard::squares(self.jumpers(side)) {
 
-                    if new_moves.len() > 0 {
-                        for i in 0..new_moves.len() {
-                            moves.push(new_moves[i]);
-                        }
-                    }
-                }
+            for mv in self.piece_moves(self.layout.pos(sq)) {
+                best = cmp::max(best, self.capture_rank(&mv));
             }
         }
 
-        if self.check_lower_left(x_pos, y_pos).1 {
+        return best;
+    }
 
-            if !empty {
+    // a row counted from the back rank of the given side
+    fn own_row_mask(&self, side:Color, row:usize) -> Bitboard {
 
-                let new_pos:Pos = Pos::new(x_pos + 2, y_pos - 2, None);
+        if side == Color::White {
+            return self.layout.row_mask(self.row - 1 - row);
+        }
 
-                if !self.check_piece_history(new_pos, &h) {
-                    if self.state[x_pos + 2][y_pos - 2] == 0 && self.state[x_pos + 1][y_pos - 1] != 0 && self.state[x_pos + 1][y_pos - 1] != self.state[x_pos][y_pos] {
+        return self.layout.row_mask(row);
+    }
 
-                        let mut _x = (x_pos + 1) as i32;
-                        let mut _y = (y_pos - 1) as i32;
+    fn promotion_row(&self, side:Color) -> Bitboard {
 
-                        _x = _x.abs();
-                        _y = _y.abs();
+        let mut mask:Bitboard = 0;
 
-                        let take_piece:Piece = Piece::new(_x as usize, _y as usize);
+        for row in self.rules.geometry.promotion_rows.iter() {
+            mask |= self.own_row_mask(side.opposite(), *row);
+        }
 
-                        _x = (x_pos + 2) as i32;
-                        _y = (y_pos - 2) as i32;
+        return mask;
+    }
 
-                        _x = _x.abs();
-                        _y = _y.abs();
+    #[allow(clippy::too_many_arguments)]
+    fn capture_sequences(&self, side:Color, is_king:bool, curr:Bitboard, last_dir:Option<usize>, empty:Bitboard, opponent:Bitboard, partial:&mut Move, moves:&mut Vec<Move>) {
 
-                        let poss_move:Pos = Pos::new(_x as usize, _y as usize, Some(take_piece));
+        let mut extended:bool = false;
 
-                        moves.push(poss_move);
-                        h.push((x_pos, y_pos));
+        let flying:bool = is_king && self.rules.flying_kings;
 
-                        let new_moves:Vec<Pos> = self.possible_moves(poss_move, Some(&h));
+        for dir in self.capture_directions(side, is_king) {
 
-                        if new_moves.len() > 0 {
-                            for i in 0..new_moves.len() {
-                                moves.push(new_moves[i]);
-                            }
-                        }
-                    }
-                }
-            } else {
+            // a sequence never turns straight back on itself
+            if last_dir == Some(self.layout.reverse(dir)) {
+                continue;
+            }
 
-                if self.state[x_pos + 2][y_pos - 2] == 0 && self.state[x_pos + 1][y_pos - 1] != 0 && self.state[x_pos + 1][y_pos - 1] != self.state[x_pos][y_pos] {
+            let mut ray:Bitboard = self.layout.step(curr, dir);
 
-                    let mut _x = (x_pos + 1) as i32;
-                    let mut _y = (y_pos - 1) as i32;
+            while flying && ray & empty != 0 {
+                ray = self.layout.step(ray, dir);
+            }
 
-                    _x = _x.abs();
-                    _y = _y.abs();
+            let targets:Bitboard = if is_king { opponent } else { self.men_targets(side, opponent) };
 
-                    let take_piece:Piece = Piece::new(_x as usize, _y as usize);
+            let mid:Bitboard = ray & targets;
 
-                    _x = (x_pos + 2) as i32;
-                    _y = (y_pos - 2) as i32;
+            if mid == 0 {
+                continue;
+            }
 
-                    _x = _x.abs();
-                    _y = _y.abs();
+            // a flying king may land on any empty square beyond the captured piece
+            let mut next:Bitboard = self.layout.step(mid, dir) & empty;
 
-                    let poss_move:Pos = Pos::new(_x as usize, _y as usize, Some(take_piece));
+            let after:Bitboard = if self.rules.remove_captured_at_end { empty } else { empty | mid };
 
-                    moves.push(poss_move);
+            while next != 0 {
 
-                    let mut his:Vec<(usize, usize)> = Vec::new();
+                extended = true;
 
-                    his.push((x_pos, y_pos));
+                partial.path.push(self.layout.pos(next.trailing_zeros() as usize));
+                partial.captured.push(self.layout.pos(mid.trailing_zeros() as usize));
 
-                    let new_moves:Vec<Pos> = self.possible_moves(poss_move, Some(&his));
+                let crowned:bool = !is_king && next & self.promotion_row(side) != 0;
 
-                    if new_moves.len() > 0 {
-                        for i in 0..new_moves.len() {
-                            moves.push(new_moves[i]);
-                        }
+                match self.rules.promotion {
+
+                    PromotionRule::EndsMove if crowned => {
+                        moves.push(partial.clone());
+                    },
+
+                    PromotionRule::ContinuesAsKing if crowned => {
+                        self.capture_sequences(side, true, next, Some(dir), after, opponent & !mid, partial, moves);
+                    },
+
+                    _ => {
+                        self.capture_sequences(side, is_king, next, Some(dir), after, opponent & !mid, partial, moves);
                     }
                 }
-            }
-        }
 
-        if self.check_lower_right(x_pos, y_pos).1 {
+                partial.path.pop();
+                partial.captured.pop();
 
-            if !empty {
+                if !flying {
+                    break;
+                }
 
-                let new_pos:Pos = Pos::new(x_pos + 2, y_pos + 2, None);
+                next = self.layout.step(next, dir) & empty;
+            }
+        }
 
-                if !self.check_piece_history(new_pos, &h) {
-                    if self.state[x_pos + 2][y_pos + 2] == 0 && self.state[x_pos + 1][y_pos + 1] != 0 && self.state[x_pos + 1][y_pos + 1] != self.state[x_pos][y_pos] {
+        if !extended && !partial.path.is_empty() {
+            moves.push(partial.clone());
+        }
+    }
 
-                        let mut _x = (x_pos + 1) as i32;
-                        let mut _y = (y_pos + 1) as i32;
+    pub fn possible_moves(&mut self, piece:Pos) -> Vec<Move> {
 
-                        _x = _x.abs();
-                        _y = _y.abs();
+        let mut moves:Vec<Move> = self.piece_moves(piece);
 
-                        let take_piece:Piece = Piece::new(_x as usize, _y as usize);
+        let side:Color = match self.piece_at(piece) {
+            Some(_p) => _p.color,
+            None => return moves
+        };
 
-                        _x = (x_pos + 2) as i32;
-                        _y = (y_pos + 2) as i32;
+        if self.majority_applies(side) {
 
-                        _x = _x.abs();
-                        _y = _y.abs();
+            let best:CaptureRank = self.best_capture(side);
 
-                        let poss_move:Pos = Pos::new(_x as usize, _y as usize, Some(take_piece));
+            moves.retain(|m| self.capture_rank(m) == best);
+        }
 
-                        moves.push(poss_move);
-                        h.push((x_pos, y_pos));
+        return moves;
+    }
 
-                        let new_moves:Vec<Pos> = self.possible_moves(poss_move, Some(&h));
+    fn piece_moves(&self, piece:Pos) -> Vec<Move> {
 
-                        if new_moves.len() > 0 {
-                            for i in 0..new_moves.len() {
-                                moves.push(new_moves[i]);
-                            }
-                        }
-                    }
-                }
-            } else {
+        let mut moves:Vec<Move> = Vec::new();
 
-                if self.state[x_pos + 2][y_pos + 2] == 0 && self.state[x_pos + 1][y_pos + 1] != 0 && self.state[x_pos + 1][y_pos + 1] != self.state[x_pos][y_pos] {
+        let side:Color;
+        let is_king:bool;
 
-                    let mut _x = (x_pos + 1) as i32;
-                    let mut _y = (y_pos + 1) as i32;
+        match self.piece_at(piece) {
 
-                    _x = _x.abs();
-                    _y = _y.abs();
+            Some(_p) => {
+                side = _p.color;
+                is_king = _p.is_king();
+            },
 
-                    let take_piece:Piece = Piece::new(_x as usize, _y as usize);
+            None => return moves
+        }
 
-                    _x = (x_pos + 2) as i32;
-                    _y = (y_pos + 2) as i32;
+        let from_bit:Bitboard = self.layout.bit(piece);
 
-                    _x = _x.abs();
-                    _y = _y.abs();
+        // the moving piece leaves its square, so it counts as empty while a
+        // sequence is traced
+        let empty:Bitboard = self.empty() | from_bit;
 
-                    let poss_move:Pos = Pos::new(_x as usize, _y as usize, Some(take_piece));
+        let mut partial:Move = Move::capture(piece, Vec::new(), Vec::new());
 
-                    moves.push(poss_move);
+        self.capture_sequences(side, is_king, from_bit, None, empty, self.pieces(side.opposite()), &mut partial, &mut moves);
 
-                    let mut his:Vec<(usize, usize)> = Vec::new();
+        // when huffing, a piece that can capture may still make a simple move
+        if moves.is_empty() || self.rules.capture_rule == CaptureRule::Huff {
 
-                    his.push((x_pos, y_pos));
+            if self.rules.capture_rule == CaptureRule::Mandatory && self.jumpers(side) != 0 {
+                return moves;
+            }
 
-                    let new_moves:Vec<Pos> = self.possible_moves(poss_move, Some(&his));
+            let dirs:Vec<usize> = if is_king { self.king_moves.clone() } else { self.man_directions(side) };
 
-                    if new_moves.len() > 0 {
-                        for i in 0..new_moves.len() {
-                            moves.push(new_moves[i]);
-                        }
-                    }
-                }
-            }
-        }
+            for dir in dirs {
 
-        if moves.len() == 0 {
+                let mut next:Bitboard = self.layout.step(from_bit, dir) & empty;
 
-            if self.check_lower_right(x_pos, y_pos).0 && empty {
-                if self.state[x_pos + 1][y_pos + 1] == 0 {
-                    if self.state[x_pos][y_pos] == self.op_side || self.state[x_pos][y_pos] > self.my_side {
-                        
-                        let poss_move:Pos = Pos::new(x_pos + 1, y_pos + 1, None);
-                        moves.push(poss_move);
-                    }
-                }
-            }
+                while next != 0 {
 
-            if self.check_upper_right(x_pos, y_pos).0 && empty {
-                if self.state[x_pos - 1][y_pos + 1] == 0 {
-                    if self.state[x_pos][y_pos] == self.my_side || self.state[x_pos][y_pos] < self.op_side {
-                        
-                        let poss_move:Pos = Pos::new(x_pos - 1, y_pos + 1, None);
-                        moves.push(poss_move);
-                    }
-                }
-            }
+                    moves.push(Move::new(piece, self.layout.pos(next.trailing_zeros() as usize)));
 
-            if self.check_upper_left(x_pos, y_pos).0 && empty {
-                if self.state[x_pos - 1][y_pos - 1] == 0 {
-                    if self.state[x_pos][y_pos] == self.my_side || self.state[x_pos][y_pos] < self.op_side {
-                        
-                        let poss_move:Pos = Pos::new(x_pos - 1, y_pos - 1, None);
-                        moves.push(poss_move);
+                    if !is_king || !self.rules.flying_kings {
+                        break;
                     }
-                }
-            }
 
-            if self.check_lower_left(x_pos, y_pos).0 && empty {
-                if self.state[x_pos + 1][y_pos - 1] == 0 {
-                    if self.state[x_pos][y_pos] == self.op_side || self.state[x_pos][y_pos] > self.my_side {
-                        
-                        let poss_move:Pos = Pos::new(x_pos + 1, y_pos - 1, None);
-                        moves.push(poss_move);
-                    }
+                    next = self.layout.step(next, dir) & empty;
                 }
             }
         }
@@ -699,140 +793,110 @@ impl Board {
         return moves;
     }
 
-    fn check_upper_left(&mut self, x:usize, y:usize) -> (bool, bool) {
+    pub fn draw_reason(&self) -> Option<DrawReason> {
 
-        let mut one_sq:bool = false;
-        let mut two_sq:bool = false;
-
-        let x_pos:i32 = x as i32;
-        let y_pos:i32 = y as i32;
-
-        if (0 <= x_pos - 2) && (0 <= y_pos - 2) && (x_pos - 2 <= 7) && (y_pos - 2 <= 7) {
-            two_sq = true;
-        }
+        let limit:usize = self.rules.no_progress_moves;
 
-        if (0 <= x_pos - 1) && (0 <= y_pos - 1) && (x_pos - 1 <= 7) && (y_pos - 1 <= 7) {
-            one_sq = true;
+        if limit > 0 && self.no_progress >= 2 * limit {
+            return Some(DrawReason::NoProgress);
         }
 
-        return (one_sq, two_sq);
-    }
+        // only positions since the last capture or man move can come back
+        let mut seen:usize = 1;
 
-    fn check_upper_right(&mut self, x:usize, y:usize) -> (bool, bool) {
+        let start:usize = self.history.len() - cmp::min(self.no_progress, self.history.len());
 
-        let mut one_sq:bool = false;
-        let mut two_sq:bool = false;
+        for i in start..self.history.len() {
 
-        let x_pos:i32 = x as i32;
-        let y_pos:i32 = y as i32;
+            let undo:&Undo = &self.history[i];
 
-        if (0 <= x_pos - 2) && (0 <= y_pos + 2) && (x_pos - 2 <= 7) && (y_pos + 2 <= 7) {
-            two_sq = true;
+            if undo.men == self.men && undo.kings == self.kings && undo.curr_player == self.curr_player {
+                seen += 1;
+            }
         }
 
-        if (0 <= x_pos - 1) && (0 <= y_pos + 1) && (x_pos - 1 <= 7) && (y_pos + 1 <= 7) {
-            one_sq = true;
+        if seen >= 3 {
+            return Some(DrawReason::Repetition);
         }
 
-        return (one_sq, two_sq);
+        return None;
     }
 
-    fn check_lower_left(&mut self, x:usize, y:usize) -> (bool, bool) {
+    pub fn game_result(&mut self) -> GameResult {
 
-        let mut one_sq:bool = false;
-        let mut two_sq:bool = false;
+        // only the side to move can be out of moves
+        if self.movers(self.curr_player) == 0 {
 
-        let x_pos:i32 = x as i32;
-        let y_pos:i32 = y as i32;
+            let side:Color = self.curr_player;
+
+            if self.rules.losing {
+                return GameResult::Win(side);
+            }
 
-        if (0 <= x_pos + 2) && (0 <= y_pos - 2) && (x_pos + 2 <= 7) && (y_pos - 2 <= 7) {
-            two_sq = true;
+            return GameResult::Win(side.opposite());
         }
 
-        if (0 <= x_pos + 1) && (0 <= y_pos - 1) && (x_pos + 1 <= 7) && (y_pos - 1 <= 7) {
-            one_sq = true;
+        if let Some(reason) = self.draw_reason() {
+            return GameResult::Draw(reason);
         }
 
-        return (one_sq, two_sq);
+        return GameResult::Ongoing;
     }
-    
-    fn check_lower_right(&mut self, x:usize, y:usize) -> (bool, bool) {
 
-        let mut one_sq:bool = false;
-        let mut two_sq:bool = false;
+    pub fn adjudicate(&self) -> GameResult {
 
-        let x_pos:i32 = x as i32;
-        let y_pos:i32 = y as i32;
+        let my_idx:usize = self.my_side.index();
+        let op_idx:usize = self.op_side.index();
 
-        if (0 <= x_pos + 2) && (0 <= y_pos + 2) && (x_pos + 2 <= 7) && (y_pos + 2 <= 7) {
-            two_sq = true;
-        }
+        // kings count as two men
+        let mut my_material:i32 = (self.men[my_idx].count_ones() + 2 * self.kings[my_idx].count_ones()) as i32;
+        let mut op_material:i32 = (self.men[op_idx].count_ones() + 2 * self.kings[op_idx].count_ones()) as i32;
 
-        if (0 <= x_pos + 1) && (0 <= y_pos + 1) && (x_pos + 1 <= 7) && (y_pos + 1 <= 7) {
-            one_sq = true;
+        // in giveaway the side with less material is ahead
+        if self.rules.losing {
+            my_material = -my_material;
+            op_material = -op_material;
         }
 
-        return (one_sq, two_sq);
-    }
-
-    pub fn finish_state(&mut self) -> (bool, i32) {
-
-        let mut finished:bool = false;
-        let mut winner:i32 = 0;
-
-        let my_pieces:Vec<Pos> = self.all_movable_pieces(self.my_side);
-        let op_pieces:Vec<Pos> = self.all_movable_pieces(self.op_side);
-
-        if self.my_pieces.len() < self.op_pieces.len() && self.op_pieces.len() == 0 {
-            finished = true;
-            winner = self.my_side;
-        } else if self.my_pieces.len() > self.op_pieces.len() && self.my_pieces.len() == 0 {
-            finished = true;
-            winner = self.op_side;
-        } else if my_pieces.len() == 0 {
-            finished = true;
-            winner = self.op_side;
-        } else if op_pieces.len() == 0 {
-            finished = true;
-            winner = self.my_side;
+        if my_material > op_material {
+            return GameResult::Adjudicated(Some(self.my_side));
+        } else if op_material > my_material {
+            return GameResult::Adjudicated(Some(self.op_side));
         }
 
-        return (finished, winner);
+        return GameResult::Adjudicated(None);
     }
 
-    fn first_prior(&mut self) -> (Option<Pos>, Option<Pos>) {
+    fn first_prior(&mut self) -> Option<Move> {
 
-        let mut _piece:Option<Pos> = None;
-        let mut next_pos:Option<Pos> = None;
+        let mut next_move:Option<Move> = None;
 
         let all_pieces:Vec<Pos> = self.all_movable_pieces(self.curr_player);
 
         for i in 0..all_pieces.len() {
 
-            if let Some(_p) = _piece {
+            if next_move.is_some() {
                 break;
             }
 
-            let poss_moves:Vec<Pos> = self.possible_moves(all_pieces[i], None);
+            let poss_moves:Vec<Move> = self.possible_moves(all_pieces[i]);
 
             for j in 0..poss_moves.len() {
 
-                if let Some(_m) = poss_moves[j].piece {
+                if poss_moves[j].is_capture() {
 
-                    _piece = Some(all_pieces[i]);
-                    next_pos = Some(poss_moves[j]);
+                    next_move = Some(poss_moves[j].clone());
                     break;
                 }
             }
         }
 
-        return (_piece, next_pos);
+        return next_move;
     }
 
-    fn second_prior(&mut self) -> (Option<Pos>, Option<Pos>) {
+    fn second_prior(&mut self) -> Option<Move> {
 
-        let mut _piece:Option<Pos> = None;
-        let mut next_pos:Option<Pos> = None;
+        let mut next_move:Option<Move> = None;
 
         let my_pieces:Vec<Pos> = self.all_movable_pieces(self.my_side);
         let op_pieces:Vec<Pos> = self.all_movable_pieces(self.op_side);
@@ -840,90 +904,70 @@ impl Board {
         for i in 0..my_pieces.len() {
             for j in 0..op_pieces.len() {
 
-                if let Some(_p) = _piece {
+                if next_move.is_some() {
                     break;
                 }
 
-                let my_moves:Vec<Pos> = self.possible_moves(my_pieces[i], None);
-                let op_moves:Vec<Pos> = self.possible_moves(op_pieces[j], None);
+                let my_moves:Vec<Move> = self.possible_moves(my_pieces[i]);
+                let op_moves:Vec<Move> = self.possible_moves(op_pieces[j]);
 
                 for x in 0..my_moves.len() {
                     for y in 0..op_moves.len() {
 
-                        if my_moves[x].x == op_moves[y].x && my_moves[x].y == op_moves[y].y {
-
-                            if self.curr_player == self.op_side {
-
-                                if let Some(_m) = my_moves[x].piece {
-
-                                    if let Some(_n) = op_moves[y].piece {
-
-                                        _piece = Some(op_pieces[j]);
-                                        next_pos = Some(op_moves[y]);
-
-                                        break;
-                                    }
-                                }
-
-                            } else if self.curr_player == self.my_side {
+                        if my_moves[x].to() != op_moves[y].to() || !my_moves[x].is_capture() || !op_moves[y].is_capture() {
+                            continue;
+                        }
 
-                                if let Some(_m) = op_moves[y].piece {
+                        if self.curr_player == self.op_side {
 
-                                    if let Some(_n) = my_moves[x].piece {
+                            next_move = Some(op_moves[y].clone());
+                            break;
 
-                                        _piece = Some(my_pieces[i]);
-                                        next_pos = Some(my_moves[x]);
+                        } else if self.curr_player == self.my_side {
 
-                                        break;
-                                    }
-                                }
-                            }
+                            next_move = Some(my_moves[x].clone());
+                            break;
                         }
                     }
                 }
             }
         }
 
-        return (_piece, next_pos);
+        return next_move;
     }
 
-    fn side_prior(&mut self) -> (Option<Pos>, Option<Pos>) {
+    fn side_prior(&mut self) -> Option<Move> {
 
-        let mut _piece:Option<Pos> = None;
-        let mut next_pos:Option<Pos> = None;
+        let mut next_move:Option<Move> = None;
 
         let all_pieces:Vec<Pos> = self.all_movable_pieces(self.curr_player);
 
-        if all_pieces.len() > 0 {
+        for i in 0..all_pieces.len() {
 
-            for i in 0..all_pieces.len() {
+            if next_move.is_some() {
+                break;
+            }
 
-                if let Some(_p) = _piece {
-                    break;
-                }
+            let poss_moves:Vec<Move> = self.possible_moves(all_pieces[i]);
 
-                let poss_moves:Vec<Pos> = self.possible_moves(all_pieces[i], None);
+            for j in 0..poss_moves.len() {
 
-                for j in 0..poss_moves.len() {
+                let next_pos:Pos = poss_moves[j].to();
 
-                    if poss_moves[j].y == 0 || poss_moves[j].y == 7 {
+                if next_pos.y == 0 || next_pos.y == self.col - 1 {
 
-                        _piece = Some(all_pieces[i]);
-                        next_pos = Some(poss_moves[j]);
-                        break;
-                    }
+                    next_move = Some(poss_moves[j].clone());
+                    break;
                 }
             }
         }
 
-        return (_piece, next_pos);
+        return next_move;
     }
 
+    fn third_prior(&mut self) -> Move {
 
-    fn third_prior(&mut self) -> (Pos, Pos) {
-
-        let mut _piece:Pos = Pos::new(0, 0, None);
-        let mut next_pos:Pos = Pos::new(0, 0, None);
+        let mut next_move:Move = Move::new(Pos::new(0, 0), Pos::new(0, 0));
 
         let mut count:usize = 1;
 
@@ -935,456 +979,597 @@ impl Board {
 
             let all_pieces:Vec<Pos> = self.all_movable_pieces(self.curr_player);
 
-            if all_pieces.len() > 0 {
-
-                let idx:usize = rng.gen_range(0, all_pieces.len()) as usize;
+            if !all_pieces.is_empty() {
 
-                _piece = all_pieces[idx];
+                let idx:usize = rng.gen_range(0, all_pieces.len());
 
-                let all_moves:Vec<Pos> = self.possible_moves(_piece, None);
+                let all_moves:Vec<Move> = self.possible_moves(all_pieces[idx]);
 
-                let mv_idx:usize = rng.gen_range(0, all_moves.len()) as usize;
+                let mv_idx:usize = rng.gen_range(0, all_moves.len());
 
-                next_pos = all_moves[mv_idx];
+                next_move = all_moves[mv_idx].clone();
 
-                let op_pieces:Vec<Pos> = self.all_movable_pieces(-self.curr_player);
+                let op_pieces:Vec<Pos> = self.all_movable_pieces(self.curr_player.opposite());
 
                 for i in 0..op_pieces.len() {
 
-                    let poss_moves:Vec<Pos> = self.possible_moves(op_pieces[i], None); 
+                    let poss_moves:Vec<Move> = self.possible_moves(op_pieces[i]);
 
-                    for j in 0..poss_moves.len() {
-
-                        if poss_moves[j].x == next_pos.x && poss_moves[j].y == next_pos.y {
-                            found = true;
-                        }
+                    if poss_moves.iter().any(|m| m.to() == next_move.to()) {
+                        found = true;
                     }
-                
                 }
-                
             }
 
-            if !found || count > all_pieces.len() || all_pieces.len() == 0 {
+            if !found || count > all_pieces.len() || all_pieces.is_empty() {
                 break;
             }
 
             count += 1;
         }
 
-        return (_piece, next_pos);
+        return next_move;
     }
 
-    pub fn best_move(&mut self) -> (Pos, Pos) {
+    pub fn best_move(&mut self) -> Move {
 
-        let mut _piece:Pos = Pos::new(0, 0, None);
-        let mut next_pos:Pos = Pos::new(0, 0, None);
+        if let Some(mv) = self.first_prior() {
+            return mv;
+        }
 
-        let (_p1, _n1) = self.first_prior();
+        if let Some(mv) = self.second_prior() {
+            return mv;
+        }
 
-        if let Some(_p) = _p1 {
-            if let Some(_n) = _n1 {
-                _piece = _p;
-                next_pos = _n;
-            }
-        } else {
+        if let Some(mv) = self.side_prior() {
+            return mv;
+        }
 
-            let (_p2, _n2) = self.second_prior();
+        return self.third_prior();
+    }
 
-            if let Some(_p) = _p2 {
-                if let Some(_n) = _n2 {
-                    _piece = _p;
-                    next_pos = _n;
-                }
-            } else {
+    // the move the search picks within time_control; the depth it reached is
+    // kept in last_search
+    pub fn ultimate_move(&mut self) -> Move {
 
-                let (_p3, _n3) = self.side_prior();
+        let control:TimeControl = self.time_control;
 
-                if let Some(_p) = _p3 {
-                    if let Some(_n) = _n3 {
-                        _piece = _p;
-                        next_pos = _n;
-                    }
-                } else {
+        self.last_search = self.search(&control, &AtomicBool::new(false), |_| {});
 
-                    let (_p4, _n4) = self.third_prior();
+        let next_move:Move = match &self.last_search {
+            Some(info) => info.pv[0].clone(),
+            None => Move::new(Pos::new(0, 0), Pos::new(0, 0))
+        };
 
-                    _piece = _p4;
-                    next_pos = _n4;
-                }
+        self.set_cache_state(&next_move);
+
+        return next_move;
+    }
+
+    fn state_hash(&self, state:&[Vec<i32>]) -> String {
+
+        let flat_state:Vec<i32> = state.iter()
+                                        .flat_map(|array| array.iter())
+                                        .cloned()
+                                        .collect();
+
+        let mut hasher = DefaultHasher::new();
+
+        flat_state.hash(&mut hasher);
+
+        return hasher.finish().to_string();
+    }
+
+    fn set_cache_state(&mut self, next_move:&Move) {
+
+        let str_hash = self.state_hash(&self.to_state());
+
+        self.cache_table.insert(str_hash, next_move.coords());
+    }
+
+    pub fn get_cache_state(&mut self) -> Option<Move> {
+
+        let hash_str = self.state_hash(&self.to_state());
+
+        let v_arr:Vec<usize> = match self.cache_table.get(&hash_str) {
+            Some(_v) => _v.to_vec(),
+            None => return None
+        };
+
+        if v_arr.len() < 4 || !v_arr.len().is_multiple_of(2) {
+            return None;
+        }
+
+        let from:Pos = Pos::new(v_arr[0], v_arr[1]);
+
+        let mut path:Vec<Pos> = Vec::new();
+
+        for i in (2..v_arr.len()).step_by(2) {
+            path.push(Pos::new(v_arr[i], v_arr[i + 1]));
+        }
+
+        // older cache files only stored the origin and the final square, so
+        // fall back to the first legal move that matches those two squares
+        let legal_moves:Vec<Move> = self.legal_moves();
+
+        let mut fallback:Option<Move> = None;
+
+        for i in 0..legal_moves.len() {
+
+            if legal_moves[i].from != from {
+                continue;
+            }
+
+            if legal_moves[i].path == path {
+                return Some(legal_moves[i].clone());
+            }
+
+            if fallback.is_none() && legal_moves[i].to() == path[path.len() - 1] {
+                fallback = Some(legal_moves[i].clone());
             }
         }
 
-        return (_piece, next_pos);
+        return fallback;
     }
 
-    pub fn ultimate_move(&mut self) -> (Pos, Pos) {
+    pub(crate) fn board_heuristics(&mut self) -> i32 {
 
-        let mut best_score:i32 = -999999;
+        let mut score:i32 = 0;
 
-        let mut _piece:Pos = Pos::new(0, 0, None);
-        let mut next_pos:Pos = Pos::new(0, 0, None);
+        let my_idx:usize = self.my_side.index();
+        let op_idx:usize = self.op_side.index();
 
-        let all_pieces:Vec<Pos> = self.all_movable_pieces(self.curr_player);
+        let op_kings:i32 = self.kings[op_idx].count_ones() as i32;
+        let op_pieces:i32 = self.men[op_idx].count_ones() as i32;
+        let mut op_poss_moves:i32 = 0;
+        let mut op_taken:i32 = 0;
+
+        let my_kings:i32 = self.kings[my_idx].count_ones() as i32;
+        let my_pieces:i32 = self.men[my_idx].count_ones() as i32;
+        let mut my_poss_moves:i32 = 0;
+        let mut my_taken:i32 = 0;
+
+        let mut all_pieces:Vec<Pos> = self.all_movable_pieces(self.op_side);
 
         for i in 0..all_pieces.len() {
 
-            let moves:Vec<(i32, Pos, Pos)> = self.get_poss_moves(all_pieces[i]);
+            let poss_moves:Vec<Move> = self.possible_moves(all_pieces[i]);
 
-            for j in 0..moves.len() {
+            for j in 0..poss_moves.len() {
 
-                if moves[j].0 > best_score {
-                    best_score = moves[j].0;
-                    _piece = moves[j].1;
-                    next_pos = moves[j].2;
-                }
+                op_poss_moves += 1;
+                op_taken += poss_moves[j].captured.len() as i32;
             }
         }
 
-        //println!("Computer Move Score: {}", best_score);
+        all_pieces = self.all_movable_pieces(self.my_side);
 
-        self.set_cache_state(_piece, next_pos);
+        for i in 0..all_pieces.len() {
 
-        return (_piece, next_pos);
-    }
+            let poss_moves:Vec<Move> = self.possible_moves(all_pieces[i]);
 
-    fn get_poss_moves(&mut self, _piece:Pos) -> Vec<(i32, Pos, Pos)> {
+            for j in 0..poss_moves.len() {
 
-        let alpha:i32 = 999999;
-        let beta:i32 = -999999;
+                my_poss_moves += 1;
+                my_taken += poss_moves[j].captured.len() as i32;
+            }
+        }
 
-        let mut all_scores:Vec<(i32, Pos, Pos)> = Vec::new();
+        let king_diff:i32 = op_kings - my_kings;
+        let piece_diff:i32 = op_pieces - my_pieces;
+        let move_diff:i32 = op_poss_moves - my_poss_moves;
+        let take_diff:i32 = op_taken - my_taken;
 
-        let poss_moves:Vec<Pos> = self.possible_moves(_piece, None);
+        let mut weights:Vec<i32> = vec![1000, 100, 10, 1];
 
-        for i in 0..poss_moves.len() {
+        // in giveaway material is a liability, so every material term counts
+        // against the side that has it; mobility is still worth having
+        if self.rules.losing {
+            weights[0] = -weights[0];
+            weights[1] = -weights[1];
+            weights[3] = -weights[3];
+        }
+        let factors:Vec<i32> = vec![king_diff, take_diff, move_diff, piece_diff];
 
-            let mut _score:i32 = 0;
+        for w in 0..weights.len() {
+            score += factors[w] * weights[w];
+        }
 
-            let score:Option<i32> = self.get_cache_score(&self.state.clone(), _piece, poss_moves[i]);
+        return score;
+    }
+}
 
-            match score {
+#[cfg(test)]
+mod tests {
 
-                Some(_s) => {
+    use super::*;
+    use crate::geometry::{Geometry, SquareSet};
 
-                    all_scores.push((_s, _piece, poss_moves[i]));
-                },
+    // leaf count of the move tree, checking that every unmake_move restores
+    // the position make_move left
+    fn perft(board:&mut Board, depth:usize) -> usize {
 
-                None => {
+        if depth == 0 {
+            return 1;
+        }
 
-                    let mut temp_board:Board = self.clone_board();
+        let moves:Vec<Move> = board.legal_moves();
+        let mut nodes:usize = 0;
 
-                    temp_board.do_move(_piece, poss_moves[i]);
+        for i in 0..moves.len() {
 
-                    _score = self.minimax(temp_board, 9, false, alpha, beta);
+            let men:[Bitboard; 2] = board.men;
+            let kings:[Bitboard; 2] = board.kings;
+            let curr_player:Color = board.curr_player;
 
-                    all_scores.push((_score, _piece, poss_moves[i]));
+            board.make_move(&moves[i]);
 
-                    self.set_cache_score(&self.state.clone(), _piece, poss_moves[i], _score);
-                },
-            }
+            nodes += perft(board, depth - 1);
 
-            //println!("Move Score: {}", _score);
+            assert_eq!(board.unmake_move(), Some(moves[i].clone()));
+            assert_eq!(board.men, men);
+            assert_eq!(board.kings, kings);
+            assert_eq!(board.curr_player, curr_player);
         }
 
-        return all_scores;
+        return nodes;
     }
 
-    fn set_cache_state(&mut self, _piece:Pos, next_pos:Pos) {
+    fn perft_counts(rules:Rules, depth:usize) -> Vec<usize> {
 
-        let flat_state:Vec<i32> = self.state.iter()
-                                            .flat_map(|array| array.iter())
-                                            .cloned()
-                                            .collect();
+        let mut board:Board = Board::with_rules(rules);
 
-        let mut hasher = DefaultHasher::new();
+        board.init();
 
-        flat_state.hash(&mut hasher);
+        return (1..=depth).map(|d| perft(&mut board, d)).collect();
+    }
+
+    fn position(rules:Rules, fen:&str) -> Board {
+
+        let mut board:Board = Board::with_rules(rules);
 
-        let str_hash = hasher.finish().to_string();
+        board.from_fen(fen).unwrap();
 
-        self.cache_table.insert(str_hash, vec![_piece.x, _piece.y, next_pos.x, next_pos.y]);
+        return board;
     }
 
-    pub fn get_cache_state(&mut self) -> (Option<Pos>, Option<Pos>) {
+    fn legal(board:&mut Board) -> Vec<String> {
 
-        let mut _piece:Option<Pos> = None;
-        let mut next_pos:Option<Pos> = None;
+        let mut moves:Vec<String> = board.legal_moves().iter().map(|m| board.move_notation(m)).collect();
 
-        let flat_state:Vec<i32> = self.state.iter()
-                                            .flat_map(|array| array.iter())
-                                            .cloned()
-                                            .collect();
-        
-        let mut hasher = DefaultHasher::new();
+        moves.sort();
 
-        flat_state.hash(&mut hasher);
+        return moves;
+    }
 
-        let hash_str = hasher.finish().to_string();
+    fn play(board:&mut Board, notation:&str) {
 
-        match self.cache_table.get(&hash_str) {
+        let mv:Move = board.legal_moves().into_iter().find(|m| board.move_notation(m) == notation).unwrap();
 
-            Some(_v) => {
+        board.make_move(&mv);
+    }
 
-                let v_arr:Vec<usize> = _v.to_vec();
+    #[test]
+    fn perft_english() {
+        assert_eq!(perft_counts(Rules::english(), 7), vec![7, 49, 302, 1469, 7361, 36768, 179740]);
+    }
 
-                _piece = Some(Pos::new(v_arr[0], v_arr[1], None));
-                next_pos = Some(Pos::new(v_arr[2], v_arr[3], None));
-            },
+    #[test]
+    fn perft_international() {
+        assert_eq!(perft_counts(Rules::international(), 6), vec![9, 81, 658, 4265, 27117, 167140]);
+    }
 
-            None => {
-                self.do_nothing();
-            }
-        }
+    #[test]
+    fn perft_russian() {
+        assert_eq!(perft_counts(Rules::russian(), 6)[4..].to_vec(), vec![7482, 37986]);
+    }
 
-        return (_piece, next_pos);
+    #[test]
+    fn perft_brazilian() {
+        assert_eq!(perft_counts(Rules::brazilian(), 6)[4..].to_vec(), vec![7473, 37628]);
     }
 
-    fn get_cache_score(&mut self, state:&Vec<Vec<i32>>, _piece:Pos, next_pos:Pos) -> Option<i32> {
+    #[test]
+    fn perft_italian() {
+        assert_eq!(perft_counts(Rules::italian(), 6)[5], 36473);
+    }
 
-        let mut score:Option<i32> = None;
+    #[test]
+    fn perft_with_huffing() {
+        assert_eq!(perft_counts(Rules::with_huffing(), 6), vec![7, 49, 379, 2916, 24505, 204723]);
+    }
 
-        let moves:Vec<i32> = vec![_piece.x as i32, _piece.y as i32, next_pos.x as i32, next_pos.y as i32];
+    #[test]
+    fn men_capture_backward_only_when_allowed() {
 
-        let mut flat_state:Vec<i32> = state.iter()
-                                            .flat_map(|array| array.iter())
-                                            .cloned()
-                                            .collect();
+        // the Black man on 18 sits behind the White man on 14
+        let fen:&str = "W:W14:B4,18";
+        let backward:Rules = Rules { men_capture_backward:true, ..Rules::english() };
 
-        for i in 0..moves.len() {
-            flat_state.push(moves[i]);
-        }
+        assert_eq!(legal(&mut position(Rules::english(), fen)), vec!["14-10", "14-9"]);
+        assert_eq!(legal(&mut position(backward, fen)), vec!["14x23"]);
+    }
 
-        let mut hasher = DefaultHasher::new();
+    #[test]
+    fn promotion_mid_capture() {
 
-        flat_state.hash(&mut hasher);
+        // the man on 11 takes 7 and lands on the back rank, with 6 to take next
+        let fen:&str = "W:W11:B6,7";
+        let rules = |promotion:PromotionRule| Rules { promotion, ..Rules::russian() };
 
-        let hash_str = hasher.finish().to_string();
+        let mut board:Board = position(rules(PromotionRule::EndsMove), fen);
 
-        match self.minax_cache.get(&hash_str) {
+        assert_eq!(legal(&mut board), vec!["11x2"]);
 
-            Some(_v) => {
-                score = Some(*_v);
-            },
+        play(&mut board, "11x2");
+        assert_eq!(board.to_fen(), "B:WK2:B6");
 
-            None => {
-                self.do_nothing();
-            }
-        }
-        
-        return score;
-    }
+        // crowned on 2, the new king goes on to take 6 and, flying, may land
+        // on either square beyond it
+        let mut board:Board = position(rules(PromotionRule::ContinuesAsKing), fen);
 
-    fn do_nothing(&mut self) {
-        return;
-    }
+        assert_eq!(legal(&mut board), vec!["11x2x13", "11x2x9"]);
 
-    fn set_cache_score(&mut self, state:&Vec<Vec<i32>>, _piece:Pos, next_pos:Pos, score:i32) {
+        play(&mut board, "11x2x9");
+        assert_eq!(board.to_fen(), "B:WK9:B");
 
-        let mut flat_state:Vec<i32> = state.iter()
-                                            .flat_map(|array| array.iter())
-                                            .cloned()
-                                            .collect();
-        
-        let moves:Vec<i32> = vec![_piece.x as i32, _piece.y as i32, next_pos.x as i32, next_pos.y as i32];
+        // passing over the back rank does not crown the man
+        let mut board:Board = position(rules(PromotionRule::AtEndOnly), fen);
 
-        for i in 0..moves.len() {
-            flat_state.push(moves[i]);
-        }
+        assert_eq!(legal(&mut board), vec!["11x2x9"]);
 
-        let mut hasher = DefaultHasher::new();
+        play(&mut board, "11x2x9");
+        assert_eq!(board.to_fen(), "B:W9:B");
+    }
 
-        flat_state.hash(&mut hasher);
+    #[test]
+    fn only_the_side_to_move_can_be_stuck() {
+
+        // Black's man on 5 is blocked by 9 and 14, but it is White's turn
+        let fen:&str = "W:W9,14,K30:B5";
 
-        let hash_str = hasher.finish().to_string();
+        assert_eq!(position(Rules::english(), fen).game_result(), GameResult::Ongoing);
+        assert_eq!(position(Rules::giveaway(), fen).game_result(), GameResult::Ongoing);
 
-        self.minax_cache.insert(hash_str, score);
+        let fen:&str = "B:W9,14,K30:B5";
+
+        assert_eq!(position(Rules::english(), fen).game_result(), GameResult::Win(Color::White));
+        assert_eq!(position(Rules::giveaway(), fen).game_result(), GameResult::Win(Color::Black));
     }
 
+    #[test]
+    fn captured_pieces_block_until_the_end() {
 
-    pub fn minimax(&mut self, mut temp_board:Board, depth:i32, is_max:bool, mut alpha:i32, mut beta:i32) -> i32 {
+        // the king takes 18 first and comes back along the other diagonal
+        // through 18 on its way to 23
+        let fen:&str = "W:WK22:B6,7,18,23";
+        let lifted:Rules = Rules { remove_captured_at_end:false, ..Rules::russian() };
 
-        let (done, _winner) = temp_board.finish_state();
+        assert_eq!(legal(&mut position(Rules::russian(), fen)), vec!["22x11x2x13", "22x11x2x9", "22x15x1", "22x4", "22x8"]);
+        assert_eq!(legal(&mut position(lifted, fen)), vec!["22x11x2x13", "22x11x2x9x27", "22x11x2x9x32", "22x15x1", "22x4", "22x8"]);
+    }
 
-        let mut initial:i32 = 0;
+    #[test]
+    fn majority_rule_keeps_the_longest_captures() {
 
-        if done || depth == 0 {
-            //println!("Depth: {}", depth);
-            initial = self.board_heuristics(temp_board);
+        // 21 can take two pieces, 28 only one
+        let fen:&str = "W:W21,28:B10,17,24";
 
-        } else {
+        assert_eq!(legal(&mut position(Rules::russian(), fen)), vec!["21x14x7", "28x19"]);
+        assert_eq!(legal(&mut position(Rules::brazilian(), fen)), vec!["21x14x7"]);
+    }
 
-            if is_max {
+    #[test]
+    fn italian_capture_tie_breaks() {
 
-                initial = -999999;
-    
-                let all_pieces:Vec<Pos> = temp_board.all_movable_pieces(temp_board.curr_player);
-    
-                for i in 0..all_pieces.len() {
-    
-                    let poss_moves:Vec<Pos> = temp_board.possible_moves(all_pieces[i], None);
-    
-                    for j in 0..poss_moves.len() {
+        let most_pieces:Rules = Rules { majority:MajorityRule::MostPieces, ..Rules::italian() };
 
-                        let mut _result:i32 = 0;
+        // one piece each way: the king must capture rather than the man
+        let fen:&str = "W:W21,K23:B18,20";
 
-                        let result:Option<i32> = self.get_cache_score(&temp_board.state.clone(), all_pieces[i], poss_moves[j]);
-                        
-                        match result {
+        assert_eq!(legal(&mut position(most_pieces.clone(), fen)), vec!["21x14", "23x16"]);
+        assert_eq!(legal(&mut position(Rules::italian(), fen)), vec!["23x16"]);
 
-                            None => {
+        // two kings capturing one piece each: take the king
+        let fen:&str = "W:WK21,K23:BK18,20";
 
-                                let mut sim_board:Board = temp_board.clone_board();
+        assert_eq!(legal(&mut position(most_pieces.clone(), fen)), vec!["21x14", "23x16"]);
+        assert_eq!(legal(&mut position(Rules::italian(), fen)), vec!["21x14"]);
 
-                                sim_board.do_move(all_pieces[i], poss_moves[j]);
+        // a man and a king either way round: take the king first
+        let fen:&str = "W:WK22:B10,19,K12,K18";
 
-                                _result = self.minimax(sim_board, depth - 1, !is_max, alpha, beta);
+        assert_eq!(legal(&mut position(most_pieces, fen)), vec!["22x13x6", "22x15x8"]);
+        assert_eq!(legal(&mut position(Rules::italian(), fen)), vec!["22x13x6"]);
+    }
 
-                                self.set_cache_score(&temp_board.state.clone(), all_pieces[i], poss_moves[j], _result)
-                            },
+    #[test]
+    fn turkish_orthogonal_captures() {
 
-                            Some(_r) => {
+        let at_end:Rules = Rules { remove_captured_at_end:true, ..Rules::turkish() };
 
-                                self.quix += 1;
+        // men capture sideways
+        assert_eq!(legal(&mut position(Rules::turkish(), "W:W36:B37")), vec!["36x38"]);
 
-                                _result = _r;
-                            }
-                        }
-    
-                        initial = cmp::max(_result, initial);
-    
-                        alpha = cmp::max(alpha, initial);
-    
-                        if beta <= alpha {
-                            break;
-                        }
-                    }
-                }
-    
-            } else {
-    
-                initial = 999999;
-    
-                let all_pieces:Vec<Pos> = temp_board.all_movable_pieces(temp_board.curr_player);
-    
-                for i in 0..all_pieces.len() {
-    
-                    let poss_moves:Vec<Pos> = temp_board.possible_moves(all_pieces[i], None);
-    
-                    for j in 0..poss_moves.len() {
-    
-                        let mut _result:i32 = 0;
+        // a king may not turn back on the line it just captured along
+        assert_eq!(legal(&mut position(Rules::turkish(), "W:WK36:B34,38")), vec!["36x33", "36x39", "36x40"]);
 
-                        let result:Option<i32> = self.get_cache_score(&temp_board.state.clone(), all_pieces[i], poss_moves[j]);
-                        
-                        match result {
+        // captured pieces leave at once, so the king may cross 12 again
+        let fen:&str = "W:WK10:B12,28,30,45";
 
-                            None => {
+        assert_eq!(legal(&mut position(Rules::turkish(), fen)), vec!["10x14x46x44x12", "10x14x46x44x20", "10x14x46x44x4"]);
+        assert_eq!(legal(&mut position(at_end, fen)), vec!["10x14x46x44x20"]);
+    }
 
-                                let mut sim_board:Board = temp_board.clone_board();
+    #[test]
+    fn frisian_orthogonal_captures_and_king_value() {
 
-                                sim_board.do_move(all_pieces[i], poss_moves[j]);
+        // men capture along rows and files too
+        assert_eq!(legal(&mut position(Rules::frisian(), "W:W32:B33")), vec!["32x34"]);
 
-                                _result = self.minimax(sim_board, depth - 1, !is_max, alpha, beta);
+        // a king is worth more than a man
+        assert_eq!(legal(&mut position(Rules::frisian(), "W:W32,34:BK28,30")), vec!["32x23"]);
 
-                                self.set_cache_score(&temp_board.state.clone(), all_pieces[i], poss_moves[j], _result)
-                            },
+        // but two men are worth more than a king
+        assert_eq!(legal(&mut position(Rules::frisian(), "W:W32,34:BK28,20,30")), vec!["34x25x14"]);
+    }
 
-                            Some(_r) => {
+    #[test]
+    fn threefold_repetition() {
 
-                                self.quix += 1;
+        let mut board:Board = position(Rules::english(), "W:WK29:BK4");
+        let shuttle:[&str; 4] = ["29-25", "4-8", "25-29", "8-4"];
 
-                                _result = _r;
-                            }
-                        }
-    
-                        initial = cmp::min(_result, initial);
-    
-                        beta = cmp::min(beta, initial);
-    
-                        if beta <= alpha {
-                            break;
-                        }
-                    }
-                }
-            }
+        for i in 0..7 {
+            play(&mut board, shuttle[i % 4]);
+            assert_eq!(board.draw_reason(), None);
         }
 
-        return initial;
+        // the starting position comes up for the third time
+        play(&mut board, "8-4");
+        assert_eq!(board.draw_reason(), Some(DrawReason::Repetition));
+
+        board.unmake_move();
+        assert_eq!(board.no_progress, 7);
+        assert_eq!(board.draw_reason(), None);
     }
 
-    fn board_heuristics(&mut self, mut board:Board) -> i32 {
+    #[test]
+    fn no_progress_counts_moves_of_both_sides() {
 
-        let mut score:i32 = 0;
+        let rules:Rules = Rules { no_progress_moves:3, ..Rules::english() };
+        let mut board:Board = position(rules, "W:WK29,21:BK4,10");
 
-        let mut op_kings:i32 = 0;
-        let mut op_pieces:i32 = 0;
-        let mut op_poss_moves:i32 = 0;
-        let mut op_taken:i32 = 0;
+        play(&mut board, "29-25");
+        play(&mut board, "4-8");
+        assert_eq!(board.no_progress, 2);
 
-        let mut my_kings:i32 = 0;
-        let mut my_pieces:i32 = 0;
-        let mut my_poss_moves:i32 = 0;
-        let mut my_taken:i32 = 0;
+        // a man move starts the count again, and so does a capture
+        play(&mut board, "21-17");
+        assert_eq!(board.no_progress, 0);
 
-        for x in 0..board.row {
-            for y in 0..board.col {
-
-                if board.state[x][y] == -2 {
-                    op_kings += 1;
-                } else if board.state[x][y] == -1 {
-                    op_pieces += 1;
-                } else if board.state[x][y] == 1 {
-                    my_pieces += 1;
-                } else if board.state[x][y] == 2 {
-                    my_kings += 1
-                }
-            }
+        play(&mut board, "10-14");
+        play(&mut board, "17x10");
+        assert_eq!(board.no_progress, 0);
+
+        // three moves each without a man move or a capture
+        let shuttle:[&str; 4] = ["8-4", "25-22", "4-8", "22-25"];
+
+        for i in 0..5 {
+            play(&mut board, shuttle[i % 4]);
+            assert_eq!(board.draw_reason(), None);
         }
 
-        let mut all_pieces:Vec<Pos> = board.all_movable_pieces(-1);
+        play(&mut board, "25-22");
+        assert_eq!(board.draw_reason(), Some(DrawReason::NoProgress));
 
-        for i in 0..all_pieces.len() {
+        board.unmake_move();
+        assert_eq!(board.no_progress, 5);
+        assert_eq!(board.draw_reason(), None);
+    }
 
-            let poss_moves:Vec<Pos> = board.possible_moves(all_pieces[i], None);
+    #[test]
+    fn huffing_allows_any_move_and_removes_a_capturer() {
 
-            for j in 0..poss_moves.len() {
+        let fen:&str = "W:W22,30:B18";
 
-                op_poss_moves += 1;
+        assert_eq!(legal(&mut position(Rules::with_huffing(), fen)), vec!["22-17", "22x15", "30-25", "30-26"]);
 
-                if let Some(_p) = poss_moves[j].piece {
-                    op_taken += 1;
-                }
-            }
-        }
+        // another piece moves, so the one that could have captured is huffed
+        let mut board:Board = position(Rules::with_huffing(), fen);
 
-        all_pieces = board.all_movable_pieces(1);
+        play(&mut board, "30-26");
 
-        for i in 0..all_pieces.len() {
+        assert_eq!(board.to_fen(), "B:W26:B18");
 
-            let poss_moves:Vec<Pos> = board.possible_moves(all_pieces[i], None);
+        // the capturing piece itself moves away and is huffed where it lands
+        let mut board:Board = position(Rules::with_huffing(), fen);
 
-            for j in 0..poss_moves.len() {
+        play(&mut board, "22-17");
 
-                my_poss_moves += 1;
+        assert_eq!(board.to_fen(), "B:W30:B18");
+    }
 
-                if let Some(_p) = poss_moves[j].piece {
-                    my_taken += 1;
-                }
-            }
-        }
+    #[test]
+    fn boards_up_to_the_bitboard_size() {
 
-        let king_diff:i32 = op_kings - my_kings;
-        let piece_diff:i32 = op_pieces - my_pieces;
-        let move_diff:i32 = op_poss_moves - my_poss_moves;
-        let take_diff:i32 = op_taken - my_taken;
+        let board:Board = Board::with_rules(Rules { geometry:Geometry::square(16), ..Rules::international() });
 
-        let weights:Vec<i32> = vec![1000, 100, 10, 1];
-        let factors:Vec<i32> = vec![king_diff, take_diff, move_diff, piece_diff];
+        assert_eq!(board.square_count(), 128);
+    }
 
-        for w in 0..weights.len() {
-            score += factors[w] * weights[w];
+    #[test]
+    #[should_panic(expected = "a 12x12 board with 144 playable squares does not fit in a 128 square bitboard")]
+    fn boards_too_big_for_a_bitboard() {
+        Board::with_rules(Rules { geometry:Geometry { squares:SquareSet::All, ..Geometry::square(12) }, ..Rules::turkish() });
+    }
+    #[test]
+    fn place_and_clear() {
+
+        let mut board:Board = Board::new();
+        let man:Piece = Piece::new(Color::White, PieceKind::Man);
+
+        board.clear();
+        assert_eq!(board.to_fen(), "W:W:B");
+
+        assert_eq!(board.place(Pos::new(5, 0), man), Ok(()));
+        assert_eq!(board.place(Pos::new(0, 1), Piece::new(Color::Black, PieceKind::King)), Ok(()));
+        assert_eq!(board.to_fen(), "W:W21:BK1");
+
+        assert_eq!(board.place(Pos::new(8, 1), man), Err(SetupError::OffBoard(Pos::new(8, 1))));
+        assert_eq!(board.place(Pos::new(5, 1), man), Err(SetupError::NotPlayable(Pos::new(5, 1))));
+        assert_eq!(board.place(Pos::new(5, 0), man), Err(SetupError::Occupied(21)));
+        assert_eq!(board.place(Pos::new(0, 3), man), Err(SetupError::ManOnPromotionRow(2)));
+        assert_eq!(board.to_fen(), "W:W21:BK1");
+    }
+
+    #[test]
+    fn failed_setup_leaves_the_board_untouched() {
+
+        let mut board:Board = Board::new();
+
+        board.init();
+        play(&mut board, "22-18");
+
+        let fen:String = board.to_fen();
+        let man:Piece = Piece::new(Color::White, PieceKind::Man);
+
+        // the first piece is fine; the second is not
+        let pieces:Vec<(Pos, Piece)> = vec![(Pos::new(5, 0), man), (Pos::new(5, 1), man)];
+
+        assert_eq!(board.setup(&pieces, Color::White), Err(SetupError::NotPlayable(Pos::new(5, 1))));
+        assert_eq!(board.to_fen(), fen);
+
+        // the move history survives too
+        assert!(board.unmake_move().is_some());
+
+        let pieces:Vec<(Pos, Piece)> = vec![(Pos::new(5, 0), man), (Pos::new(0, 1), Piece::new(Color::Black, PieceKind::Man))];
+
+        assert_eq!(board.setup(&pieces, Color::Black), Ok(()));
+        assert_eq!(board.to_fen(), "B:W21:B1");
+        assert!(board.unmake_move().is_none());
+    }
+
+    // two chains of the same length that end on the same square
+    #[test]
+    fn jump_routes_to_the_same_square_are_distinct_moves() {
+
+        let mut board:Board = Board::new();
+        let mut state:Vec<Vec<i32>> = vec![vec![0; 8]; 8];
+
+        state[6][3] = 1;
+
+        for (x, y) in [(5, 2), (3, 2), (5, 4), (3, 4)].iter() {
+            state[*x][*y] = -1;
         }
 
-        return score;
+        board.from_state(&state);
+
+        let moves:Vec<Move> = board.legal_moves();
+
+        assert_eq!(moves.len(), 2);
+        assert_eq!(moves[0].from, moves[1].from);
+        assert_eq!(moves[0].to(), moves[1].to());
+        assert_ne!(moves[0].path, moves[1].path);
     }
-}
\ No newline at end of file
+}
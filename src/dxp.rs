@@ -0,0 +1,689 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::AtomicBool;
+use std::time::{Duration, Instant};
+
+use crate::board::{Board, Pos};
+use crate::error::DxpError;
+use crate::moves::Move;
+use crate::piece::{Color, Piece, PieceKind};
+use crate::result::GameResult;
+use crate::rules::Rules;
+use crate::search::{SearchInfo, TimeControl};
+
+// the port DXP engines listen on by convention
+pub const DXP_PORT:u16 = 27531;
+
+pub const DXP_VERSION:u32 = 1;
+
+// acceptance codes of GAMEACC and BACKACC
+pub const DXP_ACCEPTED:u8 = 0;
+pub const DXP_BAD_POSITION:u8 = 3;
+pub const DXP_NOT_SUPPORTED:u8 = 1;
+pub const DXP_DECLINED:u8 = 2;
+
+const NAME_LEN:usize = 32;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GameEndReason {
+    Unknown,
+    ILose,
+    Draw,
+    IWin
+}
+
+impl GameEndReason {
+
+    fn code(&self) -> u32 {
+
+        match self {
+            GameEndReason::Unknown => 0,
+            GameEndReason::ILose => 1,
+            GameEndReason::Draw => 2,
+            GameEndReason::IWin => 3
+        }
+    }
+
+    // the same end seen from the other side
+    fn mirrored(&self) -> GameEndReason {
+
+        match self {
+            GameEndReason::ILose => GameEndReason::IWin,
+            GameEndReason::IWin => GameEndReason::ILose,
+            other => *other
+        }
+    }
+}
+
+// The messages of the Draughts eXchange Protocol. Each one is a line of ASCII
+// starting with a header letter and made of fixed width fields, sent with a
+// terminating NUL. Colours are W for White and Z for Black, and squares use
+// the same numbering as Board::square_number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DxpMessage {
+    // GAMEREQ: the initiator's name, the colour the follower plays, the
+    // thinking time in minutes for the given number of moves, and a start
+    // position (side to move and one letter per square) or None for the usual one
+    GameRequest { name:String, follower_color:Color, minutes:u32, moves:u32, position:Option<(Color, String)> },
+    // GAMEACC: the follower's name and DXP_ACCEPTED or the reason for refusing
+    GameAccept { name:String, code:u8 },
+    // MOVE: seconds spent, origin, destination and the captured squares
+    Move { seconds:u32, from:usize, to:usize, captured:Vec<usize> },
+    // GAMEEND: the reason as seen by the sender, and whether it wants no more games
+    GameEnd { reason:GameEndReason, stop:bool },
+    Chat(String),
+    // BACKREQ: go back to the given move number with the given side to move
+    BackRequest { move_number:u32, to_move:Color },
+    // BACKACC: DXP_ACCEPTED, DXP_NOT_SUPPORTED or DXP_DECLINED
+    BackAccept(u8)
+}
+
+impl DxpMessage {
+
+    pub fn parse(text:&str) -> Result<DxpMessage, DxpError> {
+
+        let chars:Vec<char> = text.trim_end_matches('\0').chars().collect();
+        let malformed = || DxpError::Malformed(text.trim_end_matches('\0').to_string());
+
+        let mut pos:usize = 1;
+
+        let message:DxpMessage = match chars.first() {
+            Some('R') => {
+
+                number(&chars, &mut pos, 2).ok_or_else(malformed)?;
+
+                let name:String = field(&chars, &mut pos, NAME_LEN).ok_or_else(malformed)?.trim().to_string();
+                let follower_color:Color = color(&field(&chars, &mut pos, 1).ok_or_else(malformed)?).ok_or_else(malformed)?;
+                let minutes:u32 = number(&chars, &mut pos, 3).ok_or_else(malformed)?;
+                let moves:u32 = number(&chars, &mut pos, 3).ok_or_else(malformed)?;
+
+                let position:Option<(Color, String)> = match field(&chars, &mut pos, 1).ok_or_else(malformed)?.as_str() {
+                    "A" => None,
+                    "B" => {
+                        let to_move:Color = color(&field(&chars, &mut pos, 1).ok_or_else(malformed)?).ok_or_else(malformed)?;
+                        let squares:String = chars[pos..].iter().collect();
+                        pos = chars.len();
+                        Some((to_move, squares))
+                    },
+                    _ => return Err(malformed())
+                };
+
+                DxpMessage::GameRequest { name, follower_color, minutes, moves, position }
+            },
+            Some('A') => {
+
+                let name:String = field(&chars, &mut pos, NAME_LEN).ok_or_else(malformed)?.trim().to_string();
+                let code:u8 = number(&chars, &mut pos, 1).ok_or_else(malformed)? as u8;
+
+                DxpMessage::GameAccept { name, code }
+            },
+            Some('M') => {
+
+                let seconds:u32 = number(&chars, &mut pos, 4).ok_or_else(malformed)?;
+                let from:usize = number(&chars, &mut pos, 2).ok_or_else(malformed)? as usize;
+                let to:usize = number(&chars, &mut pos, 2).ok_or_else(malformed)? as usize;
+                let count:u32 = number(&chars, &mut pos, 2).ok_or_else(malformed)?;
+
+                let mut captured:Vec<usize> = Vec::new();
+
+                for _ in 0..count {
+                    captured.push(number(&chars, &mut pos, 2).ok_or_else(malformed)? as usize);
+                }
+
+                DxpMessage::Move { seconds, from, to, captured }
+            },
+            Some('E') => {
+
+                let reason:GameEndReason = match number(&chars, &mut pos, 1).ok_or_else(malformed)? {
+                    1 => GameEndReason::ILose,
+                    2 => GameEndReason::Draw,
+                    3 => GameEndReason::IWin,
+                    _ => GameEndReason::Unknown
+                };
+
+                let stop:bool = number(&chars, &mut pos, 1).ok_or_else(malformed)? == 1;
+
+                DxpMessage::GameEnd { reason, stop }
+            },
+            Some('C') => {
+
+                let text:String = chars[1..].iter().collect();
+
+                pos = chars.len();
+
+                DxpMessage::Chat(text)
+            },
+            Some('B') => {
+
+                let move_number:u32 = number(&chars, &mut pos, 3).ok_or_else(malformed)?;
+                let to_move:Color = color(&field(&chars, &mut pos, 1).ok_or_else(malformed)?).ok_or_else(malformed)?;
+
+                DxpMessage::BackRequest { move_number, to_move }
+            },
+            Some('K') => DxpMessage::BackAccept(number(&chars, &mut pos, 1).ok_or_else(malformed)? as u8),
+            _ => return Err(malformed())
+        };
+
+        if pos != chars.len() {
+            return Err(malformed());
+        }
+
+        return Ok(message);
+    }
+
+    // the message without its terminating NUL
+    pub fn encode(&self) -> String {
+
+        match self {
+            DxpMessage::GameRequest { name, follower_color, minutes, moves, position } => {
+
+                let mut text:String = format!("R{:02}{}{}{:03}{:03}", DXP_VERSION, pad(name), color_char(*follower_color), minutes, moves);
+
+                match position {
+                    Some((to_move, squares)) => text.push_str(&format!("B{}{}", color_char(*to_move), squares)),
+                    None => text.push('A')
+                }
+
+                return text;
+            },
+            DxpMessage::GameAccept { name, code } => format!("A{}{}", pad(name), code),
+            DxpMessage::Move { seconds, from, to, captured } => {
+
+                let mut text:String = format!("M{:04}{:02}{:02}{:02}", seconds, from, to, captured.len());
+
+                for i in 0..captured.len() {
+                    text.push_str(&format!("{:02}", captured[i]));
+                }
+
+                return text;
+            },
+            DxpMessage::GameEnd { reason, stop } => format!("E{}{}", reason.code(), if *stop { 1 } else { 0 }),
+            DxpMessage::Chat(text) => format!("C{}", text),
+            DxpMessage::BackRequest { move_number, to_move } => format!("B{:03}{}", move_number, color_char(*to_move)),
+            DxpMessage::BackAccept(code) => format!("K{}", code)
+        }
+    }
+}
+
+fn field(chars:&[char], pos:&mut usize, len:usize) -> Option<String> {
+
+    if *pos + len > chars.len() {
+        return None;
+    }
+
+    let text:String = chars[*pos..*pos + len].iter().collect();
+
+    *pos += len;
+
+    return Some(text);
+}
+
+fn number(chars:&[char], pos:&mut usize, len:usize) -> Option<u32> {
+    return field(chars, pos, len)?.trim().parse::<u32>().ok();
+}
+
+fn color(text:&str) -> Option<Color> {
+
+    match text {
+        "W" => Some(Color::White),
+        "Z" => Some(Color::Black),
+        _ => None
+    }
+}
+
+fn color_char(color:Color) -> char {
+
+    match color {
+        Color::White => 'W',
+        Color::Black => 'Z'
+    }
+}
+
+// names are padded or cut to their fixed width
+fn pad(name:&str) -> String {
+    return format!("{:<width$}", name.chars().take(NAME_LEN).collect::<String>(), width = NAME_LEN);
+}
+
+impl Board {
+
+    // one letter per square in number order: e empty, w and z men, W and Z kings
+    pub fn dxp_position(&self) -> String {
+
+        let mut squares:String = String::new();
+
+        for number in 1..=self.square_count() {
+
+            let piece:Option<Piece> = self.square_pos(number).and_then(|pos| self.piece_at(pos));
+
+            squares.push(match piece {
+                Some(Piece { color:Color::White, kind:PieceKind::Man }) => 'w',
+                Some(Piece { color:Color::Black, kind:PieceKind::Man }) => 'z',
+                Some(Piece { color:Color::White, kind:PieceKind::King }) => 'W',
+                Some(Piece { color:Color::Black, kind:PieceKind::King }) => 'Z',
+                None => 'e'
+            });
+        }
+
+        return squares;
+    }
+
+    pub fn from_dxp_position(&mut self, to_move:Color, squares:&str) -> Result<(), DxpError> {
+
+        let letters:Vec<char> = squares.chars().collect();
+
+        if letters.len() != self.square_count() {
+            return Err(DxpError::BadPosition(squares.to_string()));
+        }
+
+        let mut pieces:Vec<(Pos, Piece)> = Vec::new();
+
+        for i in 0..letters.len() {
+
+            let piece:Piece = match letters[i] {
+                'w' => Piece::new(Color::White, PieceKind::Man),
+                'z' => Piece::new(Color::Black, PieceKind::Man),
+                'W' => Piece::new(Color::White, PieceKind::King),
+                'Z' => Piece::new(Color::Black, PieceKind::King),
+                'e' => continue,
+                _ => return Err(DxpError::BadPosition(squares.to_string()))
+            };
+
+            if let Some(pos) = self.square_pos(i + 1) {
+                pieces.push((pos, piece));
+            }
+        }
+
+        return self.setup(&pieces, to_move).map_err(|_| DxpError::BadPosition(squares.to_string()));
+    }
+
+    // a MOVE message names the captured squares but not the route, which is
+    // enough to single out the move
+    pub fn dxp_move(&mut self, from:usize, to:usize, captured:&[usize]) -> Option<Move> {
+
+        let mut wanted:Vec<usize> = captured.to_vec();
+
+        wanted.sort_unstable();
+
+        let legal_moves:Vec<Move> = self.legal_moves();
+
+        for i in 0..legal_moves.len() {
+
+            let mut taken:Vec<usize> = legal_moves[i].captured.iter().filter_map(|p| self.square_number(*p)).collect();
+
+            taken.sort_unstable();
+
+            if self.square_number(legal_moves[i].from) == Some(from) && self.square_number(legal_moves[i].to()) == Some(to) && taken == wanted {
+                return Some(legal_moves[i].clone());
+            }
+        }
+
+        return None;
+    }
+
+    pub fn dxp_message(&self, mv:&Move, seconds:u32) -> DxpMessage {
+
+        DxpMessage::Move {
+            seconds,
+            from:self.square_number(mv.from).unwrap_or(0),
+            to:self.square_number(mv.to()).unwrap_or(0),
+            captured:mv.captured.iter().filter_map(|p| self.square_number(*p)).collect()
+        }
+    }
+}
+
+// A TCP connection carrying NUL terminated DXP messages.
+pub struct DxpConnection {
+    reader:BufReader<TcpStream>,
+    writer:TcpStream
+}
+
+impl DxpConnection {
+
+    pub fn new(stream:TcpStream) -> Result<DxpConnection, DxpError> {
+
+        // messages are short and answered at once, so do not wait to batch them
+        stream.set_nodelay(true)?;
+
+        let writer:TcpStream = stream.try_clone()?;
+
+        Ok(DxpConnection {
+            reader:BufReader::new(stream),
+            writer
+        })
+    }
+
+    pub fn send(&mut self, message:&DxpMessage) -> Result<(), DxpError> {
+
+        let mut bytes:Vec<u8> = message.encode().into_bytes();
+
+        bytes.push(0);
+
+        self.writer.write_all(&bytes)?;
+        self.writer.flush()?;
+
+        return Ok(());
+    }
+
+    pub fn receive(&mut self) -> Result<DxpMessage, DxpError> {
+
+        let mut bytes:Vec<u8> = Vec::new();
+
+        if self.reader.read_until(0, &mut bytes)? == 0 {
+            return Err(DxpError::Closed);
+        }
+
+        return DxpMessage::parse(&String::from_utf8_lossy(&bytes));
+    }
+}
+
+// An engine taking part in DXP matches, either waiting for game requests as
+// the follower or connecting and sending one as the initiator.
+#[derive(Debug, Clone)]
+pub struct DxpPlayer {
+    pub name:String,
+    pub rules:Rules,
+    // None shares out the thinking time agreed in the GAMEREQ
+    pub time_control:Option<TimeControl>,
+    // the thinking time offered in a GAMEREQ: minutes for this many moves
+    pub minutes:u32,
+    pub moves:u32
+}
+
+impl DxpPlayer {
+
+    pub fn new(name:&str, rules:Rules) -> DxpPlayer {
+
+        DxpPlayer {
+            name:name.to_string(),
+            rules,
+            time_control:None,
+            minutes:10,
+            moves:75
+        }
+    }
+
+    // Accepts one connection and plays every game the initiator asks for
+    // until it disconnects, returning the results from White's side.
+    pub fn serve(&self, listener:&TcpListener) -> Result<Vec<GameResult>, DxpError> {
+
+        let (stream, _) = listener.accept()?;
+
+        let mut conn:DxpConnection = DxpConnection::new(stream)?;
+
+        let mut results:Vec<GameResult> = Vec::new();
+
+        loop {
+
+            let message:DxpMessage = match conn.receive() {
+                Ok(_m) => _m,
+                Err(DxpError::Closed) => return Ok(results),
+                Err(e) => return Err(e)
+            };
+
+            match message {
+                DxpMessage::GameRequest { follower_color, minutes, moves, position, .. } => {
+
+                    let mut board:Board = Board::with_rules(self.rules.clone());
+
+                    board.init();
+
+                    if let Some((to_move, squares)) = position {
+
+                        if board.from_dxp_position(to_move, &squares).is_err() {
+                            conn.send(&DxpMessage::GameAccept { name:self.name.clone(), code:DXP_BAD_POSITION })?;
+                            continue;
+                        }
+                    }
+
+                    conn.send(&DxpMessage::GameAccept { name:self.name.clone(), code:DXP_ACCEPTED })?;
+
+                    results.push(self.play(&mut conn, &mut board, follower_color, minutes, moves)?);
+                },
+                DxpMessage::Chat(_) => {},
+                DxpMessage::GameEnd { reason, .. } => conn.send(&DxpMessage::GameEnd { reason:reason.mirrored(), stop:false })?,
+                other => return Err(DxpError::Unexpected(other.encode()))
+            }
+        }
+    }
+
+    // Connects to a follower, requests a game in which this engine plays
+    // color from the usual starting position, and plays it out.
+    pub fn connect<A:ToSocketAddrs>(&self, addr:A, color:Color) -> Result<GameResult, DxpError> {
+
+        let mut conn:DxpConnection = DxpConnection::new(TcpStream::connect(addr)?)?;
+
+        conn.send(&DxpMessage::GameRequest {
+            name:self.name.clone(),
+            follower_color:color.opposite(),
+            minutes:self.minutes,
+            moves:self.moves,
+            position:None
+        })?;
+
+        loop {
+
+            match conn.receive()? {
+                DxpMessage::GameAccept { code, .. } if code == DXP_ACCEPTED => break,
+                DxpMessage::GameAccept { code, .. } => return Err(DxpError::Refused(code)),
+                DxpMessage::Chat(_) => {},
+                other => return Err(DxpError::Unexpected(other.encode()))
+            }
+        }
+
+        let mut board:Board = Board::with_rules(self.rules.clone());
+
+        board.init();
+
+        return self.play(&mut conn, &mut board, color, self.minutes, self.moves);
+    }
+
+    // The game loop shared by both sides, from the accepted request to
+    // GAMEEND. Each period of the agreed number of moves brings another
+    // allotment of minutes.
+    fn play(&self, conn:&mut DxpConnection, board:&mut Board, me:Color, minutes:u32, moves:u32) -> Result<GameResult, DxpError> {
+
+        let clock:Duration = Duration::from_secs(minutes as u64 * 60);
+        let period:u32 = moves.max(1);
+        let mut used:Duration = Duration::from_secs(0);
+
+        // plies are counted from a position with White to move
+        let first_ply:usize = if board.curr_player == Color::White { 0 } else { 1 };
+        let mut plies:usize = first_ply;
+
+        loop {
+
+            let result:GameResult = board.game_result();
+
+            if result.is_over() {
+
+                conn.send(&DxpMessage::GameEnd { reason:end_reason(&result, me), stop:false })?;
+
+                // wait for the other side to confirm
+                loop {
+                    if let DxpMessage::GameEnd { .. } = conn.receive()? {
+                        return Ok(result);
+                    }
+                }
+            }
+
+            if board.curr_player == me {
+
+                let start:Instant = Instant::now();
+
+                // the moves this side has made, which taking moves back undoes
+                let played:u32 = (first_ply..plies).filter(|p| (p % 2 == 0) == (me == Color::White)).count() as u32;
+
+                let remaining:Duration = (clock * (played / period + 1)).saturating_sub(used);
+
+                let control:TimeControl = self.time_control.unwrap_or(TimeControl::MoveTime(remaining / (period - played % period)));
+
+                let best:Option<SearchInfo> = board.search(&control, &AtomicBool::new(false), |_| {});
+
+                used += start.elapsed();
+
+                let mv:Move = match best {
+                    Some(info) => info.pv[0].clone(),
+                    None => continue
+                };
+
+                conn.send(&board.dxp_message(&mv, start.elapsed().as_secs() as u32))?;
+
+                board.make_move(&mv);
+                plies += 1;
+
+                continue;
+            }
+
+            match conn.receive()? {
+                DxpMessage::Move { from, to, captured, .. } => {
+
+                    let mv:Move = match board.dxp_move(from, to, &captured) {
+                        Some(_m) => _m,
+                        None => {
+                            conn.send(&DxpMessage::GameEnd { reason:GameEndReason::Unknown, stop:true })?;
+                            return Err(DxpError::IllegalMove(format!("{}-{}", from, to)));
+                        }
+                    };
+
+                    board.make_move(&mv);
+                    plies += 1;
+                },
+                DxpMessage::GameEnd { reason, .. } => {
+
+                    let reason:GameEndReason = reason.mirrored();
+
+                    conn.send(&DxpMessage::GameEnd { reason, stop:false })?;
+
+                    // an end the board does not show, such as a resignation
+                    // or an agreed draw, is recorded as adjudicated
+                    return Ok(match reason {
+                        GameEndReason::IWin => GameResult::Adjudicated(Some(me)),
+                        GameEndReason::ILose => GameResult::Adjudicated(Some(me.opposite())),
+                        _ => GameResult::Adjudicated(None)
+                    });
+                },
+                DxpMessage::BackRequest { move_number, to_move } => {
+
+                    let target:usize = (move_number.max(1) as usize - 1) * 2 + if to_move == Color::White { 0 } else { 1 };
+
+                    if target < first_ply || target > plies {
+                        conn.send(&DxpMessage::BackAccept(DXP_DECLINED))?;
+                        continue;
+                    }
+
+                    while plies > target {
+                        board.unmake_move();
+                        plies -= 1;
+                    }
+
+                    conn.send(&DxpMessage::BackAccept(DXP_ACCEPTED))?;
+                },
+                DxpMessage::Chat(_) => {},
+                other => return Err(DxpError::Unexpected(other.encode()))
+            }
+        }
+    }
+}
+
+fn end_reason(result:&GameResult, me:Color) -> GameEndReason {
+
+    match result.winner() {
+        Some(color) if color == me => GameEndReason::IWin,
+        Some(_) => GameEndReason::ILose,
+        None => GameEndReason::Draw
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::net::SocketAddr;
+    use std::thread::{self, JoinHandle};
+
+    use super::*;
+    use crate::result::DrawReason;
+
+    fn round_trip(text:&str, message:DxpMessage) {
+
+        assert_eq!(DxpMessage::parse(text), Ok(message.clone()));
+        assert_eq!(message.encode(), text);
+    }
+
+    // a whole game between two players at depth 2, the follower playing Black
+    fn loopback(rules:Rules) -> (GameResult, Vec<GameResult>) {
+
+        let listener:TcpListener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr:SocketAddr = listener.local_addr().unwrap();
+
+        let mut follower:DxpPlayer = DxpPlayer::new("follower", rules.clone());
+        let mut initiator:DxpPlayer = DxpPlayer::new("initiator", rules);
+
+        follower.time_control = Some(TimeControl::Depth(2));
+        initiator.time_control = Some(TimeControl::Depth(2));
+
+        let server:JoinHandle<Vec<GameResult>> = thread::spawn(move || follower.serve(&listener).unwrap());
+
+        let result:GameResult = initiator.connect(addr, Color::White).unwrap();
+
+        return (result, server.join().unwrap());
+    }
+
+    #[test]
+    fn messages_round_trip() {
+
+        let name:String = format!("{:<32}", "alphaZero");
+
+        round_trip(&format!("R01{}Z010075A", name), DxpMessage::GameRequest {
+            name:"alphaZero".to_string(),
+            follower_color:Color::Black,
+            minutes:10,
+            moves:75,
+            position:None
+        });
+        round_trip(&format!("R01{}W005040BZ{}", name, "e".repeat(50)), DxpMessage::GameRequest {
+            name:"alphaZero".to_string(),
+            follower_color:Color::White,
+            minutes:5,
+            moves:40,
+            position:Some((Color::Black, "e".repeat(50)))
+        });
+        round_trip(&format!("A{}0", name), DxpMessage::GameAccept { name:"alphaZero".to_string(), code:DXP_ACCEPTED });
+        round_trip("M0012322700", DxpMessage::Move { seconds:12, from:32, to:27, captured:Vec::new() });
+        round_trip("M00032819022313", DxpMessage::Move { seconds:3, from:28, to:19, captured:vec![23, 13] });
+        round_trip("E31", DxpMessage::GameEnd { reason:GameEndReason::IWin, stop:true });
+        round_trip("E20", DxpMessage::GameEnd { reason:GameEndReason::Draw, stop:false });
+        round_trip("Chello there", DxpMessage::Chat("hello there".to_string()));
+        round_trip("B012Z", DxpMessage::BackRequest { move_number:12, to_move:Color::Black });
+        round_trip("K2", DxpMessage::BackAccept(DXP_DECLINED));
+
+        // the terminating NUL is dropped when reading
+        assert_eq!(DxpMessage::parse("K0\0"), Ok(DxpMessage::BackAccept(DXP_ACCEPTED)));
+    }
+
+    #[test]
+    fn malformed_messages() {
+
+        assert_eq!(DxpMessage::parse("X1"), Err(DxpError::Malformed("X1".to_string())));
+        assert_eq!(DxpMessage::parse("E3"), Err(DxpError::Malformed("E3".to_string())));
+        assert_eq!(DxpMessage::parse("K01"), Err(DxpError::Malformed("K01".to_string())));
+        assert_eq!(DxpMessage::parse("M00123227022"), Err(DxpError::Malformed("M00123227022".to_string())));
+    }
+
+    #[test]
+    fn loopback_match_international() {
+
+        let (result, served) = loopback(Rules::international());
+
+        assert_eq!(result, GameResult::Win(Color::Black));
+        assert_eq!(served, vec![result]);
+    }
+
+    #[test]
+    fn loopback_match_english() {
+
+        let (result, served) = loopback(Rules::english());
+
+        assert_eq!(result, GameResult::Draw(DrawReason::NoProgress));
+        assert_eq!(served, vec![result]);
+    }
+}
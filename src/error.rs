@@ -0,0 +1,150 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+use crate::board::Pos;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SetupError {
+    OffBoard(Pos),
+    // a light square, or any square the rules do not play on
+    NotPlayable(Pos),
+    // playable squares are reported by their square number
+    Occupied(usize),
+    ManOnPromotionRow(usize)
+}
+
+impl fmt::Display for SetupError {
+
+    fn fmt(&self, f:&mut fmt::Formatter) -> fmt::Result {
+
+        match self {
+            SetupError::OffBoard(pos) => write!(f, "square ({}, {}) is off the board", pos.x, pos.y),
+            SetupError::NotPlayable(pos) => write!(f, "square ({}, {}) is not a playable square", pos.x, pos.y),
+            SetupError::Occupied(square) => write!(f, "square {} already has a piece on it", square),
+            SetupError::ManOnPromotionRow(square) => write!(f, "man on {} is on its promotion row and should be a king", square)
+        }
+    }
+}
+
+impl Error for SetupError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FenError {
+    // the turn and the piece lists are separated by colons
+    MissingFields(String),
+    BadTurn(String),
+    BadColor(String),
+    BadSquare(String),
+    // a square number outside 1..=squares on this board
+    SquareOutOfRange(usize, usize),
+    Setup(SetupError)
+}
+
+impl fmt::Display for FenError {
+
+    fn fmt(&self, f:&mut fmt::Formatter) -> fmt::Result {
+
+        match self {
+            FenError::MissingFields(fen) => write!(f, "expected a position like W:W21,22:B1,2 but got \"{}\"", fen),
+            FenError::BadTurn(turn) => write!(f, "side to move must be W or B, not \"{}\"", turn),
+            FenError::BadColor(field) => write!(f, "piece list \"{}\" must start with W or B", field),
+            FenError::BadSquare(square) => write!(f, "\"{}\" is not a square number, a K followed by one, or a range like 1-12", square),
+            FenError::SquareOutOfRange(square, max) => write!(f, "square {} is out of range, squares are numbered 1 to {}", square, max),
+            FenError::Setup(e) => write!(f, "illegal position: {}", e)
+        }
+    }
+}
+
+impl Error for FenError {}
+
+impl From<SetupError> for FenError {
+
+    fn from(e:SetupError) -> FenError {
+        return FenError::Setup(e);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayError {
+    // the FEN tag of the game could not be set up
+    Fen(FenError),
+    BadMove { move_number:usize, notation:String },
+    IllegalMove { move_number:usize, notation:String },
+    // a short capture that matches more than one legal route
+    AmbiguousMove { move_number:usize, notation:String }
+}
+
+impl fmt::Display for ReplayError {
+
+    fn fmt(&self, f:&mut fmt::Formatter) -> fmt::Result {
+
+        match self {
+            ReplayError::Fen(e) => write!(f, "bad FEN tag: {}", e),
+            ReplayError::BadMove { move_number, notation } => write!(f, "move {} \"{}\" is not a move in square numbers or coordinates", move_number, notation),
+            ReplayError::IllegalMove { move_number, notation } => write!(f, "move {} \"{}\" is not legal in this position", move_number, notation),
+            ReplayError::AmbiguousMove { move_number, notation } => write!(f, "move {} \"{}\" matches more than one capture route; give every square", move_number, notation)
+        }
+    }
+}
+
+impl Error for ReplayError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PdnError {
+    // games are numbered from 1 in the order they appear in the file
+    Syntax { game:usize, line:usize, message:String },
+    Replay { game:usize, error:ReplayError }
+}
+
+impl fmt::Display for PdnError {
+
+    fn fmt(&self, f:&mut fmt::Formatter) -> fmt::Result {
+
+        match self {
+            PdnError::Syntax { game, line, message } => write!(f, "game {}, line {}: {}", game, line, message),
+            PdnError::Replay { game, error } => write!(f, "game {}: {}", game, error)
+        }
+    }
+}
+
+impl Error for PdnError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DxpError {
+    Io(String),
+    // the other side closed the connection
+    Closed,
+    Malformed(String),
+    BadPosition(String),
+    // a MOVE message that matches no legal move, as from-to
+    IllegalMove(String),
+    // the GAMEREQ was turned down with this acceptance code
+    Refused(u8),
+    Unexpected(String)
+}
+
+impl fmt::Display for DxpError {
+
+    fn fmt(&self, f:&mut fmt::Formatter) -> fmt::Result {
+
+        match self {
+            DxpError::Io(e) => write!(f, "connection error: {}", e),
+            DxpError::Closed => write!(f, "the connection was closed"),
+            DxpError::Malformed(message) => write!(f, "malformed DXP message \"{}\"", message),
+            DxpError::BadPosition(position) => write!(f, "\"{}\" is not a position on this board", position),
+            DxpError::IllegalMove(mv) => write!(f, "move {} is not legal in this position", mv),
+            DxpError::Refused(code) => write!(f, "game request refused with code {}", code),
+            DxpError::Unexpected(message) => write!(f, "unexpected DXP message \"{}\"", message)
+        }
+    }
+}
+
+impl Error for DxpError {}
+
+impl From<io::Error> for DxpError {
+
+    fn from(e:io::Error) -> DxpError {
+        return DxpError::Io(e.to_string());
+    }
+}
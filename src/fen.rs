@@ -0,0 +1,185 @@
+use crate::board::{Board, Pos};
+use crate::error::FenError;
+use crate::piece::{Color, Piece, PieceKind};
+
+// Positions in the PDN FEN tag format, e.g. W:W21,22,K30:B1,2,K12 for White
+// to move with men on 21 and 22 and a king on 30 against Black men on 1 and 2
+// and a king on 12. Ranges such as B1-12 are accepted when reading.
+impl Board {
+
+    pub fn from_fen(&mut self, fen:&str) -> Result<(), FenError> {
+
+        let fen:&str = fen.trim().trim_end_matches('.');
+
+        let fields:Vec<&str> = fen.split(':').map(|f| f.trim()).collect();
+
+        if fields.len() < 2 {
+            return Err(FenError::MissingFields(fen.to_string()));
+        }
+
+        let to_move:Color = match fields[0] {
+            "W" | "w" => Color::White,
+            "B" | "b" => Color::Black,
+            _ => return Err(FenError::BadTurn(fields[0].to_string()))
+        };
+
+        let mut pieces:Vec<(Pos, Piece)> = Vec::new();
+
+        for i in 1..fields.len() {
+
+            let color:Color = match fields[i].chars().next() {
+                Some('W') | Some('w') => Color::White,
+                Some('B') | Some('b') => Color::Black,
+                _ => return Err(FenError::BadColor(fields[i].to_string()))
+            };
+
+            for square in fields[i][1..].split(',').map(|s| s.trim()) {
+
+                if square.is_empty() {
+                    continue;
+                }
+
+                let (kind, numbers) = match square.strip_prefix(|c| c == 'K' || c == 'k') {
+                    Some(rest) => (PieceKind::King, rest),
+                    None => (PieceKind::Man, square)
+                };
+
+                let bounds:Vec<&str> = numbers.split('-').collect();
+
+                let mut range:Vec<usize> = Vec::new();
+
+                for j in 0..bounds.len() {
+
+                    match bounds[j].trim().parse::<usize>() {
+                        Ok(number) if bounds.len() <= 2 => range.push(number),
+                        _ => return Err(FenError::BadSquare(square.to_string()))
+                    }
+                }
+
+                if range[0] > range[range.len() - 1] {
+                    return Err(FenError::BadSquare(square.to_string()));
+                }
+
+                for number in range[0]..=range[range.len() - 1] {
+
+                    match self.square_pos(number) {
+                        Some(pos) => pieces.push((pos, Piece::new(color, kind))),
+                        None => return Err(FenError::SquareOutOfRange(number, self.square_count()))
+                    }
+                }
+            }
+        }
+
+        self.setup(&pieces, to_move)?;
+
+        return Ok(());
+    }
+
+    pub fn to_fen(&self) -> String {
+
+        let turn:&str = if self.curr_player == Color::White { "W" } else { "B" };
+
+        let mut fen:String = turn.to_string();
+
+        for color in [Color::White, Color::Black].iter() {
+
+            let mut squares:Vec<String> = Vec::new();
+
+            for number in 1..=self.square_count() {
+
+                let pos:Pos = match self.square_pos(number) {
+                    Some(_p) => _p,
+                    None => continue
+                };
+
+                match self.piece_at(pos) {
+                    Some(piece) if piece.color == *color && piece.is_king() => squares.push(format!("K{}", number)),
+                    Some(piece) if piece.color == *color => squares.push(number.to_string()),
+                    _ => {}
+                }
+            }
+
+            fen.push(':');
+            fen.push_str(if *color == Color::White { "W" } else { "B" });
+            fen.push_str(&squares.join(","));
+        }
+
+        return fen;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::error::SetupError;
+    use crate::rules::Rules;
+
+    fn read(rules:Rules, fen:&str) -> Result<String, FenError> {
+
+        let mut board:Board = Board::with_rules(rules);
+
+        board.from_fen(fen)?;
+
+        return Ok(board.to_fen());
+    }
+
+    #[test]
+    fn start_positions_round_trip() {
+
+        let variants:Vec<Rules> = vec![Rules::english(), Rules::international(), Rules::russian(), Rules::brazilian(), Rules::italian(), Rules::turkish(), Rules::frisian()];
+
+        for i in 0..variants.len() {
+
+            let mut board:Board = Board::with_rules(variants[i].clone());
+
+            board.init();
+
+            let fen:String = board.to_fen();
+
+            assert_eq!(read(variants[i].clone(), &fen), Ok(fen.clone()));
+        }
+
+        let mut board:Board = Board::with_rules(Rules::english());
+
+        board.init();
+
+        assert_eq!(board.to_fen(), "W:W21,22,23,24,25,26,27,28,29,30,31,32:B1,2,3,4,5,6,7,8,9,10,11,12");
+    }
+
+    #[test]
+    fn ranges_kings_and_turn() {
+
+        assert_eq!(read(Rules::english(), "B:W21-23,K5:BK30,1."), Ok("B:WK5,21,22,23:B1,K30".to_string()));
+        assert_eq!(read(Rules::english(), "w:w9:b"), Ok("W:W9:B".to_string()));
+    }
+
+    #[test]
+    fn bad_positions() {
+
+        assert_eq!(read(Rules::english(), "W"), Err(FenError::MissingFields("W".to_string())));
+        assert_eq!(read(Rules::english(), "X:W21"), Err(FenError::BadTurn("X".to_string())));
+        assert_eq!(read(Rules::english(), "W:X21"), Err(FenError::BadColor("X21".to_string())));
+        assert_eq!(read(Rules::english(), "W:W2x"), Err(FenError::BadSquare("2x".to_string())));
+        assert_eq!(read(Rules::english(), "W:W1-2-3"), Err(FenError::BadSquare("1-2-3".to_string())));
+        assert_eq!(read(Rules::english(), "W:W12-5"), Err(FenError::BadSquare("12-5".to_string())));
+        assert_eq!(read(Rules::english(), "W:W33"), Err(FenError::SquareOutOfRange(33, 32)));
+        assert_eq!(read(Rules::english(), "W:W0"), Err(FenError::SquareOutOfRange(0, 32)));
+    }
+
+    #[test]
+    fn illegal_setups_name_the_square() {
+
+        let occupied:FenError = read(Rules::english(), "W:W21:B21").unwrap_err();
+
+        assert_eq!(occupied, FenError::Setup(SetupError::Occupied(21)));
+        assert_eq!(occupied.to_string(), "illegal position: square 21 already has a piece on it");
+
+        let promoted:FenError = read(Rules::english(), "W:W1").unwrap_err();
+
+        assert_eq!(promoted, FenError::Setup(SetupError::ManOnPromotionRow(1)));
+        assert_eq!(promoted.to_string(), "illegal position: man on 1 is on its promotion row and should be a king");
+
+        assert_eq!(read(Rules::international(), "W:W30:BK30"), Err(FenError::Setup(SetupError::Occupied(30))));
+    }
+}
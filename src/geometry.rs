@@ -0,0 +1,52 @@
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SquareSet {
+    // the dark squares, with a dark square in the bottom left corner
+    Standard,
+    // the other colour, with a light square in the bottom left corner (Italian)
+    Mirrored,
+    // every square (Turkish)
+    All
+}
+
+// The shape of the board. Rows are given from each side's own point of view:
+// row 0 is that side's back rank, so the same lists describe both sides.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Geometry {
+    pub rows:usize,
+    pub cols:usize,
+    pub squares:SquareSet,
+    // rows filled with men at the start
+    pub start_rows:Vec<usize>,
+    // rows of the opponent's side on which a man is crowned
+    pub promotion_rows:Vec<usize>
+}
+
+impl Geometry {
+
+    // a square board with the dark squares played and every row but the two
+    // in the middle filled with men
+    pub fn square(size:usize) -> Geometry {
+
+        Geometry {
+            rows:size,
+            cols:size,
+            squares:SquareSet::Standard,
+            start_rows:(0..size / 2 - 1).collect(),
+            promotion_rows:vec![0]
+        }
+    }
+
+    pub fn square_count(&self) -> usize {
+        return (0..self.rows).map(|x| (0..self.cols).filter(|y| self.is_playable(x, *y)).count()).sum();
+    }
+
+    pub fn is_playable(&self, x:usize, y:usize) -> bool {
+
+        // the bottom left corner is (rows - 1, 0)
+        match self.squares {
+            SquareSet::Standard => (x + y) % 2 == (self.rows - 1) % 2,
+            SquareSet::Mirrored => (x + y) % 2 != (self.rows - 1) % 2,
+            SquareSet::All => true
+        }
+    }
+}
@@ -0,0 +1,551 @@
+use std::io::{BufRead, Write};
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::board::Board;
+use crate::moves::Move;
+use crate::pdn::PdnGame;
+use crate::rules::{Rules, VARIANTS};
+use crate::search::{SearchInfo, TimeControl};
+
+type Output = Arc<Mutex<Box<dyn Write + Send>>>;
+
+// a search running on its own thread, with the flags the command loop uses to
+// end it
+struct Running {
+    handle:JoinHandle<()>,
+    stop:Arc<AtomicBool>,
+    pondering:Arc<AtomicBool>,
+    // the deepest iteration to run and the deepest one finished so far
+    max_depth:Arc<AtomicI32>,
+    reached:Arc<AtomicI32>
+}
+
+// A text engine protocol modelled on Hub, the one used by draughts GUIs and
+// tournament managers. Commands are read one per line and answered with
+// key=value lines:
+//
+//   hub                      id, params and wait
+//   set-param name=variant value=russian
+//   init                     ready
+//   new-game                 back to the starting position
+//   pos fen=W:W21,22:B1,2 moves="22-17 2-7"
+//   level depth=8 | move-time=2.5 | time=60 inc=1 | infinite
+//   go think | go ponder | go analyze
+//   ponder-hit, stop, ping (pong), quit
+//
+// While searching the engine streams info lines with depth, score, nodes,
+// time and pv, and ends with done move=... ponder=...
+pub struct Hub {
+    board:Board,
+    level:TimeControl,
+    out:Output,
+    search:Option<Running>
+}
+
+impl Hub {
+
+    pub fn new(rules:Rules, output:Box<dyn Write + Send>) -> Hub {
+
+        let mut board:Board = Board::with_rules(rules);
+
+        board.init();
+
+        Hub {
+            board,
+            level:TimeControl::Depth(crate::board::SEARCH_DEPTH),
+            out:Arc::new(Mutex::new(output)),
+            search:None
+        }
+    }
+
+    // reads commands until quit or the end of the input
+    pub fn run<R:BufRead>(&mut self, input:R) {
+
+        for line in input.lines() {
+
+            let line:String = match line {
+                Ok(_l) => _l,
+                Err(_) => break
+            };
+
+            if !self.command(&line) {
+                break;
+            }
+        }
+
+        self.stop();
+    }
+
+    // handles one line; false once the engine should quit
+    pub fn command(&mut self, line:&str) -> bool {
+
+        let (name, args) = parse_line(line);
+
+        match name.as_str() {
+            "" => {},
+            "hub" => {
+                send(&self.out, &format!("id name=alphaZero version={}", env!("CARGO_PKG_VERSION")));
+                send(&self.out, &format!("param name=variant value={} type=enum values=\"{}\"", self.variant(), VARIANTS.iter().map(|v| v.0).collect::<Vec<&str>>().join(" ")));
+                send(&self.out, "wait");
+            },
+            "init" => send(&self.out, "ready"),
+            "ping" => send(&self.out, "pong"),
+            "set-param" => self.set_param(&args),
+            "new-game" => {
+                self.stop();
+                self.set_position(None, "");
+            },
+            "pos" => {
+                self.stop();
+                self.set_position(arg(&args, "fen"), arg(&args, "moves").unwrap_or(""));
+            },
+            "level" => self.set_level(&args),
+            "go" => self.go(&args),
+            "ponder-hit" => self.ponder_hit(),
+            "stop" => self.stop(),
+            "quit" => return false,
+            _ => error(&self.out, &format!("unknown command {}", name))
+        }
+
+        return true;
+    }
+
+    fn variant(&self) -> &str {
+
+        for i in 0..VARIANTS.len() {
+
+            if (VARIANTS[i].1)() == self.board.rules {
+                return VARIANTS[i].0;
+            }
+        }
+
+        return "custom";
+    }
+
+    fn set_param(&mut self, args:&[(String, String)]) {
+
+        match (arg(args, "name"), arg(args, "value")) {
+            (Some("variant"), Some(value)) => match Rules::from_name(value) {
+                Some(rules) => {
+                    self.stop();
+                    self.board = Board::with_rules(rules);
+                    self.board.init();
+                },
+                None => error(&self.out, &format!("unknown variant {}", value))
+            },
+            (Some(name), _) => error(&self.out, &format!("unknown param {}", name)),
+            _ => error(&self.out, "set-param needs name= and value=")
+        }
+    }
+
+    fn set_position(&mut self, fen:Option<&str>, moves:&str) {
+
+        let mut game:PdnGame = PdnGame::new();
+
+        if let Some(fen) = fen {
+            game.set_tag("FEN", fen);
+        }
+
+        game.moves = moves.split_whitespace().map(|m| m.to_string()).collect();
+
+        let mut board:Board = Board::with_rules(self.board.rules.clone());
+
+        match board.replay(&game) {
+            Ok(_) => self.board = board,
+            Err(e) => error(&self.out, &e.to_string())
+        }
+    }
+
+    fn set_level(&mut self, args:&[(String, String)]) {
+
+        let seconds = |key:&str| arg(args, key).and_then(|v| v.parse::<f64>().ok()).filter(|s| *s >= 0.0).map(Duration::from_secs_f64);
+
+        let level:Option<TimeControl> = if arg(args, "infinite").is_some() {
+            Some(TimeControl::Infinite)
+        } else if let Some(depth) = arg(args, "depth") {
+            depth.parse::<i32>().ok().filter(|d| *d > 0).map(TimeControl::Depth)
+        } else if arg(args, "move-time").is_some() {
+            seconds("move-time").map(TimeControl::MoveTime)
+        } else if arg(args, "time").is_some() {
+            seconds("time").map(|remaining| TimeControl::Clock { remaining, increment:seconds("inc").unwrap_or_default() })
+        } else {
+            None
+        };
+
+        match level {
+            Some(_l) => self.level = _l,
+            None => error(&self.out, "level needs depth=, move-time=, time= [inc=] or infinite")
+        }
+    }
+
+    fn go(&mut self, args:&[(String, String)]) {
+
+        if let Some(running) = &self.search {
+
+            if !running.handle.is_finished() {
+                error(&self.out, "already searching");
+                return;
+            }
+        }
+
+        self.stop();
+
+        // pondering and analysis run until stop; thinking uses the level
+        let pondering:bool = arg(args, "ponder").is_some() || arg(args, "analyze").is_some();
+
+        let stop:Arc<AtomicBool> = Arc::new(AtomicBool::new(false));
+        let ponder_flag:Arc<AtomicBool> = Arc::new(AtomicBool::new(pondering));
+
+        let mut board:Board = self.board.clone();
+        let control:TimeControl = if pondering { TimeControl::Infinite } else { self.level };
+
+        let max_depth:Arc<AtomicI32> = Arc::new(AtomicI32::new(control.max_depth()));
+        let reached:Arc<AtomicI32> = Arc::new(AtomicI32::new(0));
+
+        let out:Output = self.out.clone();
+        let thread_stop:Arc<AtomicBool> = stop.clone();
+        let thread_ponder:Arc<AtomicBool> = ponder_flag.clone();
+        let thread_max_depth:Arc<AtomicI32> = max_depth.clone();
+        let thread_reached:Arc<AtomicI32> = reached.clone();
+
+        let handle:JoinHandle<()> = thread::spawn(move || {
+
+            let start:Instant = Instant::now();
+
+            // move notation only depends on the board shape
+            let names:Board = Board::with_rules(board.rules.clone());
+
+            let best:Option<SearchInfo> = board.search(&control, &thread_stop, |info| {
+
+                send(&out, &info_line(&names, info, start.elapsed()));
+
+                thread_reached.store(info.depth, Ordering::SeqCst);
+
+                // a ponder hit can lower the depth after the search started
+                if info.depth >= thread_max_depth.load(Ordering::SeqCst) {
+                    thread_stop.store(true, Ordering::Relaxed);
+                }
+            });
+
+            // a finished ponder or analysis waits to be told to stop
+            while thread_ponder.load(Ordering::Relaxed) && !thread_stop.load(Ordering::Relaxed) {
+                thread::sleep(Duration::from_millis(10));
+            }
+
+            send(&out, &done_line(&board, best.as_ref()));
+        });
+
+        self.search = Some(Running { handle, stop, pondering:ponder_flag, max_depth, reached });
+    }
+
+    // the opponent played the expected move: keep searching within the level
+    fn ponder_hit(&mut self) {
+
+        let running:&Running = match &self.search {
+            Some(running) if running.pondering.swap(false, Ordering::Relaxed) => running,
+            _ => return
+        };
+
+        let stop:Arc<AtomicBool> = running.stop.clone();
+
+        // the ponder search has no depth limit or deadline of its own
+        running.max_depth.store(self.level.max_depth(), Ordering::SeqCst);
+
+        if running.reached.load(Ordering::SeqCst) >= self.level.max_depth() {
+            stop.store(true, Ordering::Relaxed);
+        }
+
+        if let Some(budget) = self.level.budget() {
+
+            thread::spawn(move || {
+                thread::sleep(budget);
+                stop.store(true, Ordering::Relaxed);
+            });
+        }
+    }
+
+    // ends the running search, which sends its done line first
+    fn stop(&mut self) {
+
+        if let Some(running) = self.search.take() {
+            running.stop.store(true, Ordering::Relaxed);
+            let _ = running.handle.join();
+        }
+    }
+}
+
+fn send(out:&Output, line:&str) {
+
+    if let Ok(mut writer) = out.lock() {
+        let _ = writeln!(writer, "{}", line);
+        let _ = writer.flush();
+    }
+}
+
+fn error(out:&Output, message:&str) {
+    send(out, &format!("error message=\"{}\"", message.replace('"', "'")));
+}
+
+fn pv_notation(board:&Board, pv:&[Move]) -> String {
+    return pv.iter().map(|m| board.move_notation(m)).collect::<Vec<String>>().join(" ");
+}
+
+fn info_line(board:&Board, info:&SearchInfo, elapsed:Duration) -> String {
+
+    let seconds:f64 = elapsed.as_secs_f64();
+    let nps:u64 = if seconds > 0.0 { (info.nodes as f64 / seconds) as u64 } else { 0 };
+
+    return format!("info depth={} score={} nodes={} time={:.3} nps={} pv=\"{}\"", info.depth, info.score, info.nodes, seconds, nps, pv_notation(board, &info.pv));
+}
+
+fn done_line(board:&Board, best:Option<&SearchInfo>) -> String {
+
+    let pv:&[Move] = match best {
+        Some(info) => &info.pv,
+        None => return "done".to_string()
+    };
+
+    let mut line:String = format!("done move={}", board.move_notation(&pv[0]));
+
+    if pv.len() > 1 {
+        line.push_str(&format!(" ponder={}", board.move_notation(&pv[1])));
+    }
+
+    return line;
+}
+
+// the command word and its key=value arguments, values optionally in quotes;
+// a bare word is a key with an empty value
+fn parse_line(line:&str) -> (String, Vec<(String, String)>) {
+
+    let chars:Vec<char> = line.trim().chars().collect();
+
+    let mut words:Vec<(String, String)> = Vec::new();
+    let mut i:usize = 0;
+
+    while i < chars.len() {
+
+        if chars[i].is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        let mut key:String = String::new();
+
+        while i < chars.len() && !chars[i].is_whitespace() && chars[i] != '=' {
+            key.push(chars[i]);
+            i += 1;
+        }
+
+        let mut value:String = String::new();
+
+        if i < chars.len() && chars[i] == '=' {
+
+            i += 1;
+
+            if i < chars.len() && chars[i] == '"' {
+
+                i += 1;
+
+                while i < chars.len() && chars[i] != '"' {
+                    value.push(chars[i]);
+                    i += 1;
+                }
+
+                i += 1;
+
+            } else {
+
+                while i < chars.len() && !chars[i].is_whitespace() {
+                    value.push(chars[i]);
+                    i += 1;
+                }
+            }
+        }
+
+        words.push((key, value));
+    }
+
+    if words.is_empty() {
+        return (String::new(), words);
+    }
+
+    let name:String = words.remove(0).0;
+
+    return (name, words);
+}
+
+fn arg<'a>(args:&'a [(String, String)], key:&str) -> Option<&'a str> {
+    return args.iter().find(|a| a.0 == key).map(|a| a.1.as_str());
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    // a writer the test can read back while the hub owns it
+    #[derive(Clone)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+
+        fn write(&mut self, bytes:&[u8]) -> std::io::Result<usize> {
+            return self.0.lock().unwrap().write(bytes);
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            return Ok(());
+        }
+    }
+
+    fn hub(rules:Rules) -> (Hub, Shared) {
+
+        let output:Shared = Shared(Arc::new(Mutex::new(Vec::new())));
+
+        return (Hub::new(rules, Box::new(output.clone())), output);
+    }
+
+    fn lines(output:&Shared) -> Vec<String> {
+        return String::from_utf8_lossy(&output.0.lock().unwrap()).lines().map(|l| l.to_string()).collect();
+    }
+
+    // waits for the running search to send done by itself
+    fn finishes(hub:&Hub) -> bool {
+
+        let start:Instant = Instant::now();
+
+        while start.elapsed() < Duration::from_secs(10) {
+
+            if hub.search.as_ref().is_none_or(|s| s.handle.is_finished()) {
+                return true;
+            }
+
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        return false;
+    }
+
+    #[test]
+    fn parse_line_reads_words_and_quoted_values() {
+
+        let (name, args) = parse_line("  pos fen=W:W21,22:B1,2 moves=\"22-17 2-7\" ");
+
+        assert_eq!(name, "pos");
+        assert_eq!(args, vec![("fen".to_string(), "W:W21,22:B1,2".to_string()), ("moves".to_string(), "22-17 2-7".to_string())]);
+
+        let (name, args) = parse_line("go ponder");
+
+        assert_eq!(name, "go");
+        assert_eq!(args, vec![("ponder".to_string(), String::new())]);
+
+        assert_eq!(parse_line("   "), (String::new(), Vec::new()));
+    }
+
+    #[test]
+    fn set_level_reads_every_limit() {
+
+        let (mut hub, output) = hub(Rules::english());
+
+        hub.command("level depth=8");
+        assert_eq!(hub.level, TimeControl::Depth(8));
+
+        hub.command("level move-time=2.5");
+        assert_eq!(hub.level, TimeControl::MoveTime(Duration::from_millis(2500)));
+
+        hub.command("level time=60 inc=1");
+        assert_eq!(hub.level, TimeControl::Clock { remaining:Duration::from_secs(60), increment:Duration::from_secs(1) });
+
+        hub.command("level infinite");
+        assert_eq!(hub.level, TimeControl::Infinite);
+
+        // a bad level is reported and leaves the old one
+        hub.command("level depth=0");
+        assert_eq!(hub.level, TimeControl::Infinite);
+        assert_eq!(lines(&output), vec!["error message=\"level needs depth=, move-time=, time= [inc=] or infinite\""]);
+    }
+
+    #[test]
+    fn hub_lists_the_variants() {
+
+        let (mut hub, output) = hub(Rules::english());
+
+        hub.command("set-param name=variant value=russian");
+        hub.command("hub");
+
+        assert_eq!(lines(&output)[1], "param name=variant value=russian type=enum values=\"english huffing international russian brazilian canadian frisian italian turkish giveaway\"");
+        assert_eq!(hub.board.rules, Rules::russian());
+    }
+
+    #[test]
+    fn pos_plays_the_moves() {
+
+        let (mut hub, output) = hub(Rules::english());
+
+        hub.command("pos moves=\"22-18 11-15\"");
+        assert_eq!(hub.board.to_fen(), "W:W18,21,23,24,25,26,27,28,29,30,31,32:B1,2,3,4,5,6,7,8,9,10,12,15");
+
+        hub.command("pos fen=W:W18:B15 moves=\"18x11\"");
+        assert_eq!(hub.board.to_fen(), "B:W11:B");
+
+        // an illegal move keeps the last position
+        hub.command("pos moves=\"22-19\"");
+        assert_eq!(hub.board.to_fen(), "B:W11:B");
+        assert_eq!(lines(&output), vec!["error message=\"move 1 '22-19' is not legal in this position\""]);
+    }
+
+    #[test]
+    fn go_think_reports_and_finishes() {
+
+        let (mut hub, output) = hub(Rules::english());
+
+        hub.command("level depth=3");
+        hub.command("go think");
+
+        assert!(finishes(&hub));
+
+        hub.command("stop");
+
+        let lines:Vec<String> = lines(&output);
+
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("info depth=1 "));
+        assert!(lines[2].starts_with("info depth=3 "));
+
+        let done:&str = lines[3].strip_prefix("done move=").unwrap().split(' ').next().unwrap();
+        let mut board:Board = Board::with_rules(Rules::english());
+
+        board.init();
+
+        assert!(board.legal_moves().iter().any(|m| board.move_notation(m) == done));
+    }
+
+    #[test]
+    fn ponder_waits_for_stop_and_ponder_hit_keeps_the_level() {
+
+        let (mut hub, output) = hub(Rules::english());
+
+        hub.command("level depth=3");
+
+        // pondering only ends when told to
+        hub.command("go ponder");
+        thread::sleep(Duration::from_millis(50));
+
+        assert!(!hub.search.as_ref().unwrap().handle.is_finished());
+
+        hub.command("stop");
+
+        assert!(lines(&output).last().unwrap().starts_with("done move="));
+
+        // after a ponder hit the depth limit applies again
+        hub.command("go ponder");
+        hub.command("ponder-hit");
+
+        assert!(finishes(&hub));
+        assert!(lines(&output).last().unwrap().starts_with("done move="));
+    }
+}
@@ -0,0 +1,29 @@
+#![allow(non_snake_case)]
+#![allow(clippy::needless_return, clippy::needless_range_loop)]
+
+pub mod bitboard;
+pub mod board;
+pub mod dxp;
+pub mod error;
+pub mod fen;
+pub mod geometry;
+pub mod hub;
+pub mod moves;
+pub mod pdn;
+pub mod piece;
+pub mod result;
+pub mod rules;
+pub mod search;
+
+pub use bitboard::Bitboard;
+pub use board::{Board, CacheTable, Pos};
+pub use dxp::{DxpMessage, DxpPlayer};
+pub use error::{DxpError, FenError, PdnError, ReplayError, SetupError};
+pub use geometry::{Geometry, SquareSet};
+pub use hub::Hub;
+pub use moves::Move;
+pub use pdn::PdnGame;
+pub use piece::{Color, Piece, PieceKind};
+pub use result::{DrawReason, GameResult};
+pub use rules::{CaptureRule, MajorityRule, PromotionRule, Rules};
+pub use search::{SearchInfo, TimeControl};
@@ -0,0 +1,49 @@
+use crate::board::Pos;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Move {
+    pub from:Pos,
+    pub path:Vec<Pos>,
+    pub captured:Vec<Pos>
+}
+
+impl Move {
+
+    pub fn new(from:Pos, to:Pos) -> Move {
+
+        Move {
+            from,
+            path:vec![to],
+            captured:Vec::new()
+        }
+    }
+
+    pub fn capture(from:Pos, path:Vec<Pos>, captured:Vec<Pos>) -> Move {
+
+        Move {
+            from,
+            path,
+            captured
+        }
+    }
+
+    pub fn to(&self) -> Pos {
+        return self.path[self.path.len() - 1];
+    }
+
+    pub fn is_capture(&self) -> bool {
+        return !self.captured.is_empty();
+    }
+
+    pub fn coords(&self) -> Vec<usize> {
+
+        let mut coords:Vec<usize> = vec![self.from.x, self.from.y];
+
+        for i in 0..self.path.len() {
+            coords.push(self.path[i].x);
+            coords.push(self.path[i].y);
+        }
+
+        return coords;
+    }
+}
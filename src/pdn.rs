@@ -0,0 +1,801 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::board::{Board, Pos};
+use crate::error::{PdnError, ReplayError};
+use crate::moves::Move;
+use crate::piece::{Color, Piece};
+use crate::result::GameResult;
+use crate::rules::Rules;
+
+const RESULT_TOKENS:[&str; 8] = ["2-0", "0-2", "1-1", "1-0", "0-1", "0-0", "1/2-1/2", "*"];
+
+// A game in Portable Draughts Notation: the tag pairs followed by the moves in
+// square numbers, with `-` for a simple move and `x` between every square of a
+// capture, and the result.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PdnGame {
+    pub tags:Vec<(String, String)>,
+    pub moves:Vec<String>,
+    // the number the file gives each move; empty for games built in code
+    pub numbers:Vec<usize>,
+    pub result:String
+}
+
+impl PdnGame {
+
+    pub fn new() -> PdnGame {
+
+        PdnGame {
+            tags:Vec::new(),
+            moves:Vec::new(),
+            numbers:Vec::new(),
+            result:"*".to_string()
+        }
+    }
+
+    pub fn tag(&self, name:&str) -> Option<&str> {
+        return self.tags.iter().find(|t| t.0 == name).map(|t| t.1.as_str());
+    }
+
+    pub fn set_tag(&mut self, name:&str, value:&str) {
+
+        match self.tags.iter_mut().find(|t| t.0 == name) {
+            Some(tag) => tag.1 = value.to_string(),
+            None => self.tags.push((name.to_string(), value.to_string()))
+        }
+    }
+
+    pub fn to_pdn(&self) -> String {
+
+        let mut pdn:String = String::new();
+
+        for i in 0..self.tags.len() {
+            pdn.push_str(&format!("[{} \"{}\"]\n", self.tags[i].0, self.tags[i].1.replace('\\', "\\\\").replace('"', "\\\"")));
+        }
+
+        pdn.push('\n');
+
+        let mut tokens:Vec<String> = Vec::new();
+
+        let offset:usize = if black_opens(self) { 1 } else { 0 };
+
+        for i in 0..self.moves.len() {
+
+            // keep each move number on the same line as its move
+            if i == 0 && offset == 1 {
+                tokens.push(format!("1... {}", self.moves[i]));
+            } else if (i + offset).is_multiple_of(2) {
+                tokens.push(format!("{}. {}", (i + offset) / 2 + 1, self.moves[i]));
+            } else {
+                tokens.push(self.moves[i].clone());
+            }
+        }
+
+        tokens.push(self.result.clone());
+
+        let mut line:String = String::new();
+
+        for i in 0..tokens.len() {
+
+            if !line.is_empty() && line.len() + tokens[i].len() >= 80 {
+                pdn.push_str(&line);
+                pdn.push('\n');
+                line.clear();
+            }
+
+            if !line.is_empty() {
+                line.push(' ');
+            }
+
+            line.push_str(&tokens[i]);
+        }
+
+        pdn.push_str(&line);
+        pdn.push_str("\n\n");
+
+        return pdn;
+    }
+}
+
+impl Board {
+
+    pub fn move_notation(&self, mv:&Move) -> String {
+
+        let separator:&str = if mv.is_capture() { "x" } else { "-" };
+
+        let mut squares:Vec<String> = vec![self.square_number(mv.from).unwrap_or(0).to_string()];
+
+        for i in 0..mv.path.len() {
+            squares.push(self.square_number(mv.path[i]).unwrap_or(0).to_string());
+        }
+
+        return squares.join(separator);
+    }
+
+    // Plays the moves of a game from its FEN tag, or from the starting
+    // position when there is none, and returns them. The board keeps its own
+    // rules, so pick them with rules_for first.
+    pub fn replay(&mut self, game:&PdnGame) -> Result<Vec<Move>, ReplayError> {
+
+        match game.tag("FEN") {
+            Some(fen) => self.from_fen(fen).map_err(ReplayError::Fen)?,
+            None => {
+
+                self.init();
+
+                let mut pieces:Vec<(Pos, Piece)> = Vec::new();
+
+                for number in 1..=self.square_count() {
+
+                    if let Some(pos) = self.square_pos(number) {
+                        if let Some(piece) = self.piece_at(pos) {
+                            pieces.push((pos, piece));
+                        }
+                    }
+                }
+
+                self.setup(&pieces, first_to_move(game)).map_err(|e| ReplayError::Fen(e.into()))?;
+            }
+        }
+
+        // games built in code are numbered the way to_pdn writes them
+        let offset:usize = if black_opens(game) { 1 } else { 0 };
+
+        let mut moves:Vec<Move> = Vec::new();
+
+        for i in 0..game.moves.len() {
+
+            let move_number:usize = game.numbers.get(i).copied().unwrap_or((i + offset) / 2 + 1);
+
+            let mv:Move = self.find_move(&game.moves[i], move_number)?;
+
+            if !self.do_move(&mv) {
+                return Err(ReplayError::IllegalMove { move_number, notation:game.moves[i].clone() });
+            }
+
+            moves.push(mv);
+        }
+
+        return Ok(moves);
+    }
+
+    // Matches a move in square numbers (11-15, 22x15x8) or coordinates (c3-d4,
+    // c3:e5) against the legal moves. A capture may give only its first and
+    // last squares as long as that picks out a single route.
+    fn find_move(&mut self, notation:&str, move_number:usize) -> Result<Move, ReplayError> {
+
+        let parts:Vec<&str> = notation.split(&['-', 'x', ':'][..]).collect();
+
+        let mut squares:Vec<Pos> = Vec::new();
+
+        for i in 0..parts.len() {
+
+            match self.notation_square(parts[i]) {
+                Some(pos) => squares.push(pos),
+                None => return Err(ReplayError::BadMove { move_number, notation:notation.to_string() })
+            }
+        }
+
+        if squares.len() < 2 {
+            return Err(ReplayError::BadMove { move_number, notation:notation.to_string() });
+        }
+
+        let last:Pos = squares[squares.len() - 1];
+
+        let candidates:Vec<Move> = self.legal_moves().into_iter()
+                                        .filter(|mv| mv.from == squares[0] && mv.to() == last)
+                                        .filter(|mv| squares.len() == 2 || mv.path == squares[1..].to_vec())
+                                        .collect();
+
+        if candidates.is_empty() {
+            return Err(ReplayError::IllegalMove { move_number, notation:notation.to_string() });
+        }
+
+        // routes that take the same pieces lead to the same position
+        for i in 1..candidates.len() {
+
+            let mut taken:Vec<Pos> = candidates[i].captured.clone();
+            let mut first:Vec<Pos> = candidates[0].captured.clone();
+
+            taken.sort_by_key(|p| (p.x, p.y));
+            first.sort_by_key(|p| (p.x, p.y));
+
+            if taken != first {
+                return Err(ReplayError::AmbiguousMove { move_number, notation:notation.to_string() });
+            }
+        }
+
+        return Ok(candidates[0].clone());
+    }
+
+    fn notation_square(&self, square:&str) -> Option<Pos> {
+
+        if let Ok(number) = square.parse::<usize>() {
+            return self.square_pos(number);
+        }
+
+        // coordinates: a file letter from the left and a rank from the bottom
+        let file:char = square.chars().next()?;
+
+        if !file.is_ascii_lowercase() {
+            return None;
+        }
+
+        let rank:usize = square[1..].parse::<usize>().ok()?;
+
+        if rank == 0 || rank > self.row {
+            return None;
+        }
+
+        let pos:Pos = Pos::new(self.row - rank, (file as u8 - b'a') as usize);
+
+        self.square_number(pos)?;
+
+        return Some(pos);
+    }
+}
+
+// Reads every game in a PDN file. Comments, variations, NAGs and move
+// strength marks are skipped; games are split at their result token or where
+// a new set of tags starts.
+pub fn parse_pdn(text:&str) -> Result<Vec<PdnGame>, PdnError> {
+
+    let chars:Vec<char> = text.chars().collect();
+
+    let mut games:Vec<PdnGame> = Vec::new();
+    let mut game:PdnGame = PdnGame::new();
+    let mut in_moves:bool = false;
+
+    // the last move number read, and the moves played since it
+    let mut number:usize = 1;
+    let mut ply:usize = 0;
+
+    let mut line:usize = 1;
+    let mut i:usize = 0;
+
+    while i < chars.len() {
+
+        let c:char = chars[i];
+
+        if c == '\n' {
+            line += 1;
+            i += 1;
+            continue;
+        }
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        let game_number:usize = games.len() + 1;
+        let start_line:usize = line;
+
+        match c {
+            '[' => {
+
+                if in_moves {
+                    games.push(game);
+                    game = PdnGame::new();
+                    in_moves = false;
+                    number = 1;
+                    ply = 0;
+                }
+
+                let (name, value) = read_tag(&chars, &mut i, &mut line)
+                                        .map_err(|message| PdnError::Syntax { game:games.len() + 1, line:start_line, message })?;
+
+                game.set_tag(&name, &value);
+            },
+            '{' => {
+
+                if !skip_comment(&chars, &mut i, &mut line) {
+                    return Err(PdnError::Syntax { game:game_number, line:start_line, message:"comment is never closed".to_string() });
+                }
+            },
+            '(' => {
+
+                if !skip_variation(&chars, &mut i, &mut line) {
+                    return Err(PdnError::Syntax { game:game_number, line:start_line, message:"variation is never closed".to_string() });
+                }
+            },
+            // escaped lines and rest-of-line comments
+            '%' | ';' => {
+
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            },
+            ']' | '}' | ')' => {
+                return Err(PdnError::Syntax { game:game_number, line, message:format!("unexpected '{}'", c) });
+            },
+            _ => {
+
+                let start:usize = i;
+
+                while i < chars.len() && !chars[i].is_whitespace() && !"[]{}()".contains(chars[i]) {
+                    i += 1;
+                }
+
+                let token:String = chars[start..i].iter().collect();
+
+                if RESULT_TOKENS.contains(&token.as_str()) {
+
+                    game.result = token;
+                    games.push(game);
+                    game = PdnGame::new();
+                    in_moves = false;
+                    number = 1;
+                    ply = 0;
+                    continue;
+                }
+
+                if token.starts_with('$') {
+                    continue;
+                }
+
+                // a move number, perhaps glued to its move; 12... or a lone
+                // ... after 12. means the second move of that number comes next
+                let (prefix, notation) = match token.rfind('.') {
+                    Some(dot) => (&token[..dot + 1], &token[dot + 1..]),
+                    None => ("", token.as_str())
+                };
+
+                if let Ok(n) = prefix.trim_end_matches('.').parse::<usize>() {
+                    number = n;
+                    ply = 0;
+                }
+
+                if prefix.ends_with("...") {
+                    ply = 1;
+                }
+
+                let notation:&str = notation.trim_end_matches(&['!', '?'][..]);
+
+                if notation.is_empty() {
+                    continue;
+                }
+
+                if !is_move_token(notation) {
+                    return Err(PdnError::Syntax { game:game_number, line, message:format!("\"{}\" is not a move, move number or result", token) });
+                }
+
+                game.moves.push(notation.to_string());
+                game.numbers.push(number + ply / 2);
+                ply += 1;
+                in_moves = true;
+            }
+        }
+    }
+
+    if in_moves || !game.tags.is_empty() {
+
+        // a game cut off before its result token keeps the result from its tags
+        if let Some(result) = game.tag("Result") {
+            game.result = result.to_string();
+        }
+
+        games.push(game);
+    }
+
+    return Ok(games);
+}
+
+// Parses a PDN file and replays every game on a board with the rules its tags
+// name, falling back to the given rules.
+pub fn replay_games(text:&str, rules:&Rules) -> Result<Vec<(PdnGame, Vec<Move>)>, PdnError> {
+
+    let games:Vec<PdnGame> = parse_pdn(text)?;
+
+    let mut replayed:Vec<(PdnGame, Vec<Move>)> = Vec::new();
+
+    for i in 0..games.len() {
+
+        let mut board:Board = Board::with_rules(rules_for(&games[i], rules));
+
+        let moves:Vec<Move> = board.replay(&games[i]).map_err(|error| PdnError::Replay { game:i + 1, error })?;
+
+        replayed.push((games[i].clone(), moves));
+    }
+
+    return Ok(replayed);
+}
+
+// the rules named by the Variant tag, or else by the GameType tag
+pub fn rules_for(game:&PdnGame, default:&Rules) -> Rules {
+
+    if let Some(rules) = game.tag("Variant").and_then(Rules::from_name) {
+        return rules;
+    }
+
+    // GameType may carry board details after the number, as in 20,W,10,10,N2,0
+    let variant:Option<&str> = game.tag("GameType")
+                                    .and_then(|t| t.split(',').next())
+                                    .and_then(|t| t.trim().parse::<u32>().ok())
+                                    .and_then(variant_name);
+
+    match variant.and_then(Rules::from_name) {
+        Some(rules) => rules,
+        None => default.clone()
+    }
+}
+
+// scores are given from White's side: 2-0 is a White win, 1-1 a draw
+pub fn result_token(result:&GameResult) -> String {
+
+    if !result.is_over() {
+        return "*".to_string();
+    }
+
+    match result.winner() {
+        Some(Color::White) => "2-0".to_string(),
+        Some(Color::Black) => "0-2".to_string(),
+        None => "1-1".to_string()
+    }
+}
+
+// a game set up with Black to move opens with 1. ... so that every number
+// starts with a White move
+fn black_opens(game:&PdnGame) -> bool {
+    return game.tag("FEN").is_some_and(|fen| fen.trim_start().starts_with(['B', 'b']));
+}
+
+// the GameType tag numbers of the PDN standard
+pub fn game_type(variant:&str) -> Option<u32> {
+
+    match variant.to_lowercase().as_str() {
+        "international" => Some(20),
+        "english" | "huffing" | "giveaway" => Some(21),
+        "italian" => Some(22),
+        "russian" => Some(25),
+        "brazilian" => Some(26),
+        "canadian" => Some(27),
+        "turkish" => Some(30),
+        "frisian" => Some(40),
+        _ => None
+    }
+}
+
+// The side that opens a game without a FEN tag: the colour field of GameType
+// when it has one, else Black for English checkers and White elsewhere, as the
+// PDN standard gives them.
+fn first_to_move(game:&PdnGame) -> Color {
+
+    let fields:Vec<&str> = game.tag("GameType").unwrap_or("").split(',').map(|f| f.trim()).collect();
+
+    match fields.get(1) {
+        Some(&"W") | Some(&"w") => return Color::White,
+        Some(&"B") | Some(&"b") => return Color::Black,
+        _ => {}
+    }
+
+    let number:Option<u32> = match fields[0].parse::<u32>() {
+        Ok(number) => Some(number),
+        Err(_) => game.tag("Variant").and_then(game_type)
+    };
+
+    if number == Some(21) {
+        return Color::Black;
+    }
+
+    return Color::White;
+}
+
+pub fn variant_name(game_type:u32) -> Option<&'static str> {
+
+    match game_type {
+        20 => Some("international"),
+        21 => Some("english"),
+        22 => Some("italian"),
+        25 => Some("russian"),
+        26 => Some("brazilian"),
+        27 => Some("canadian"),
+        30 => Some("turkish"),
+        40 => Some("frisian"),
+        _ => None
+    }
+}
+
+// the date in the YYYY.MM.DD form of the Date tag
+pub fn pdn_date(time:SystemTime) -> String {
+
+    let days:i64 = match time.duration_since(UNIX_EPOCH) {
+        Ok(_d) => (_d.as_secs() / 86400) as i64,
+        Err(_) => return "????.??.??".to_string()
+    };
+
+    // civil calendar from days since 1970-01-01
+    let z:i64 = days + 719468;
+    let era:i64 = z / 146097;
+    let doe:i64 = z - era * 146097;
+    let yoe:i64 = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy:i64 = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp:i64 = (5 * doy + 2) / 153;
+    let day:i64 = doy - (153 * mp + 2) / 5 + 1;
+    let month:i64 = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year:i64 = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    return format!("{:04}.{:02}.{:02}", year, month, day);
+}
+
+// a [Name "value"] pair; i starts on the opening bracket and ends past the
+// closing one
+fn read_tag(chars:&[char], i:&mut usize, line:&mut usize) -> Result<(String, String), String> {
+
+    let mut name:String = String::new();
+    let mut value:String = String::new();
+    let mut quoted:bool = false;
+    let mut name_done:bool = false;
+    let mut seen_value:bool = false;
+
+    *i += 1;
+
+    while *i < chars.len() {
+
+        let c:char = chars[*i];
+
+        *i += 1;
+
+        if c == '\n' {
+            *line += 1;
+        }
+
+        if quoted {
+
+            match c {
+                '\\' if *i < chars.len() => {
+                    value.push(chars[*i]);
+                    *i += 1;
+                },
+                '"' => quoted = false,
+                _ => value.push(c)
+            }
+
+        } else {
+
+            match c {
+                ']' => {
+
+                    if name.is_empty() || !seen_value {
+                        return Err(format!("tag [{}] needs a name and a quoted value", name));
+                    }
+
+                    return Ok((name, value));
+                },
+                '"' => {
+                    quoted = true;
+                    seen_value = true;
+                },
+                _ if c.is_whitespace() => name_done = !name.is_empty(),
+                _ if !name_done && !seen_value => name.push(c),
+                _ => return Err(format!("unexpected '{}' in tag {}", c, name))
+            }
+        }
+    }
+
+    return Err("tag is never closed".to_string());
+}
+
+fn skip_comment(chars:&[char], i:&mut usize, line:&mut usize) -> bool {
+
+    while *i < chars.len() {
+
+        let c:char = chars[*i];
+
+        *i += 1;
+
+        if c == '\n' {
+            *line += 1;
+        } else if c == '}' {
+            return true;
+        }
+    }
+
+    return false;
+}
+
+// variations nest and may hold comments, which can contain brackets themselves
+fn skip_variation(chars:&[char], i:&mut usize, line:&mut usize) -> bool {
+
+    let mut depth:usize = 0;
+
+    while *i < chars.len() {
+
+        let c:char = chars[*i];
+
+        match c {
+            '\n' => *line += 1,
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            '{' => {
+
+                if !skip_comment(chars, i, line) {
+                    return false;
+                }
+
+                continue;
+            },
+            _ => {}
+        }
+
+        *i += 1;
+
+        if depth == 0 {
+            return true;
+        }
+    }
+
+    return false;
+}
+
+// squares as numbers or coordinates joined by -, x or :
+fn is_move_token(token:&str) -> bool {
+
+    let parts:Vec<&str> = token.split(&['-', 'x', ':'][..]).collect();
+
+    if parts.len() < 2 {
+        return false;
+    }
+
+    for i in 0..parts.len() {
+
+        let part:&str = parts[i];
+
+        let digits:&str = match part.chars().next() {
+            Some(c) if c.is_ascii_lowercase() => &part[1..],
+            _ => part
+        };
+
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return false;
+        }
+    }
+
+    return true;
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const GAMES:&str = "[Event \"first\"]
+[GameType \"21\"]
+
+1. 11-15 {a comment (with brackets)} 23-19 $1 (1... 22-18 (1... 24-20) {more} 2. 15x22)
+2. 8-11! 22-18?! 1/2-1/2
+
+[Event \"second\"]
+[GameType \"21\"]
+[FEN \"W:W22:B11,18\"]
+
+1. 22x8 2-0
+";
+
+    fn notations(moves:&[Move], game:&PdnGame) -> Vec<String> {
+
+        let mut board:Board = Board::with_rules(rules_for(game, &Rules::default()));
+        let mut notations:Vec<String> = Vec::new();
+
+        board.replay(&PdnGame { moves:Vec::new(), ..game.clone() }).unwrap();
+
+        for i in 0..moves.len() {
+            notations.push(board.move_notation(&moves[i]));
+            board.do_move(&moves[i]);
+        }
+
+        return notations;
+    }
+
+    #[test]
+    fn parse_skips_comments_variations_and_nags() {
+
+        let games:Vec<PdnGame> = parse_pdn(GAMES).unwrap();
+
+        assert_eq!(games.len(), 2);
+
+        assert_eq!(games[0].tag("Event"), Some("first"));
+        assert_eq!(games[0].moves, vec!["11-15", "23-19", "8-11", "22-18"]);
+        assert_eq!(games[0].numbers, vec![1, 1, 2, 2]);
+        assert_eq!(games[0].result, "1/2-1/2");
+
+        assert_eq!(games[1].tag("FEN"), Some("W:W22:B11,18"));
+        assert_eq!(games[1].moves, vec!["22x8"]);
+        assert_eq!(games[1].result, "2-0");
+    }
+
+    #[test]
+    fn replay_from_start_and_fen() {
+
+        let games:Vec<(PdnGame, Vec<Move>)> = replay_games(GAMES, &Rules::default()).unwrap();
+
+        assert_eq!(notations(&games[0].1, &games[0].0), vec!["11-15", "23-19", "8-11", "22-18"]);
+
+        // the short capture is filled in with its whole route
+        assert_eq!(notations(&games[1].1, &games[1].0), vec!["22x15x8"]);
+    }
+
+    #[test]
+    fn unclosed_brackets() {
+
+        assert_eq!(parse_pdn("1. 11-15 {never closed").unwrap_err(), PdnError::Syntax { game:1, line:1, message:"comment is never closed".to_string() });
+        assert_eq!(parse_pdn("1. 11-15 (23-19 (24-20)").unwrap_err(), PdnError::Syntax { game:1, line:1, message:"variation is never closed".to_string() });
+    }
+
+    #[test]
+    fn bad_moves_name_the_game_and_move() {
+
+        let illegal:String = format!("{}[GameType \"21\"]\n\n1. 11-15 23-19 2. 8-11 24-21 *\n", GAMES);
+
+        assert_eq!(replay_games(&illegal, &Rules::default()).unwrap_err(), PdnError::Replay {
+            game:3,
+            error:ReplayError::IllegalMove { move_number:2, notation:"24-21".to_string() }
+        });
+
+        // 22x6 can go by 13 or 15, taking different pieces
+        let ambiguous:&str = "[GameType \"21\"]\n[FEN \"W:W22:B9,10,17,18\"]\n\n1. 22x6 *\n";
+
+        assert_eq!(replay_games(ambiguous, &Rules::default()).unwrap_err(), PdnError::Replay {
+            game:1,
+            error:ReplayError::AmbiguousMove { move_number:1, notation:"22x6".to_string() }
+        });
+
+        // numbers come from the file, whichever side is to move
+        let exported:&str = "[GameType \"21\"]\n[FEN \"W:W22:B1\"]\n\n1. 22-18 1-2 *\n";
+
+        assert_eq!(replay_games(exported, &Rules::default()).unwrap_err(), PdnError::Replay {
+            game:1,
+            error:ReplayError::IllegalMove { move_number:1, notation:"1-2".to_string() }
+        });
+
+        let second:&str = "[GameType \"21\"]\n[FEN \"W:W22:B9,10,17,18\"]\n\n1. ... 22x13x6 2. 1-5 *\n";
+
+        assert_eq!(replay_games(second, &Rules::default()).unwrap_err(), PdnError::Replay {
+            game:1,
+            error:ReplayError::IllegalMove { move_number:2, notation:"1-5".to_string() }
+        });
+    }
+
+    #[test]
+    fn move_numbers_follow_the_file() {
+
+        let games:Vec<PdnGame> = parse_pdn("1... 1-5 2. 22-18 5-9 3.18-14 *\n23. 11-15 ... 22-18 *\n11-15 22-18 8-11 *").unwrap();
+
+        assert_eq!(games[0].numbers, vec![1, 2, 2, 3]);
+        assert_eq!(games[1].numbers, vec![23, 23]);
+        assert_eq!(games[2].numbers, vec![1, 1, 2]);
+    }
+
+    #[test]
+    fn to_pdn_round_trips() {
+
+        let mut game:PdnGame = PdnGame::new();
+
+        game.set_tag("Event", "the \"quoted\" \\ event");
+        game.set_tag("FEN", "B:W18,22:B1,5");
+        game.moves = vec!["1-6".to_string(), "22-17".to_string(), "5-9".to_string()];
+        game.result = "0-2".to_string();
+
+        let pdn:String = game.to_pdn();
+
+        assert_eq!(pdn, "[Event \"the \\\"quoted\\\" \\\\ event\"]\n[FEN \"B:W18,22:B1,5\"]\n\n1... 1-6 2. 22-17 5-9 0-2\n\n");
+
+        let read:PdnGame = parse_pdn(&pdn).unwrap().remove(0);
+
+        assert_eq!(read.numbers, vec![1, 2, 2]);
+        assert_eq!(PdnGame { numbers:Vec::new(), ..read }, game);
+
+        // long games wrap before 80 columns without splitting a number from its move
+        let mut long:PdnGame = PdnGame::new();
+
+        for i in 0..60 {
+            long.moves.push(if i % 2 == 0 { "22x15x8x1".to_string() } else { "1x10x19x28".to_string() });
+        }
+
+        let pdn:String = long.to_pdn();
+
+        assert!(pdn.lines().all(|l| l.len() < 80 && !l.ends_with('.')));
+        assert_eq!(parse_pdn(&pdn).unwrap()[0].moves, long.moves);
+        assert_eq!(parse_pdn(&pdn).unwrap()[0].numbers, (0..60).map(|i| i / 2 + 1).collect::<Vec<usize>>());
+    }
+}
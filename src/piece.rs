@@ -0,0 +1,81 @@
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Color {
+    // starts on the bottom rows and moves up the board
+    White,
+    // starts on the top rows and moves down the board
+    Black
+}
+
+impl Color {
+
+    pub fn opposite(self) -> Color {
+
+        match self {
+            Color::White => Color::Black,
+            Color::Black => Color::White
+        }
+    }
+
+    pub fn index(self) -> usize {
+
+        match self {
+            Color::White => 0,
+            Color::Black => 1
+        }
+    }
+
+    // sign used for this color in the saved board grids
+    pub fn sign(self) -> i32 {
+
+        match self {
+            Color::White => 1,
+            Color::Black => -1
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum PieceKind {
+    Man,
+    King
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Piece {
+    pub color:Color,
+    pub kind:PieceKind
+}
+
+impl Piece {
+
+    pub fn new(color:Color, kind:PieceKind) -> Piece {
+
+        Piece {
+            color,
+            kind
+        }
+    }
+
+    pub fn is_king(&self) -> bool {
+        return self.kind == PieceKind::King;
+    }
+
+    // grid encoding: 1 for a man, 2 for a king, negative for Black
+    pub fn value(&self) -> i32 {
+
+        let magnitude:i32 = if self.is_king() { 2 } else { 1 };
+
+        return magnitude * self.color.sign();
+    }
+
+    pub fn from_value(value:i32) -> Option<Piece> {
+
+        let color:Color = if value > 0 { Color::White } else { Color::Black };
+
+        match value.abs() {
+            1 => Some(Piece::new(color, PieceKind::Man)),
+            2 => Some(Piece::new(color, PieceKind::King)),
+            _ => None
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use crate::piece::Color;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DrawReason {
+    // the same position with the same side to move came up three times
+    Repetition,
+    // neither side captured or moved a man for the configured number of moves
+    NoProgress
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GameResult {
+    Ongoing,
+    // the other side has no pieces or no moves left
+    Win(Color),
+    Draw(DrawReason),
+    // the game was stopped before it finished and scored on material; holds
+    // the side that was ahead, or None when material was level
+    Adjudicated(Option<Color>)
+}
+
+impl GameResult {
+
+    pub fn is_over(&self) -> bool {
+        return *self != GameResult::Ongoing;
+    }
+
+    pub fn winner(&self) -> Option<Color> {
+
+        match *self {
+            GameResult::Win(side) => Some(side),
+            GameResult::Adjudicated(side) => side,
+            _ => None
+        }
+    }
+
+    // 1 if the game went to `side`, -1 if it went to the other side, 0 for
+    // anything else
+    pub fn score_for(&self, side:Color) -> i32 {
+
+        match self.winner() {
+            Some(winner) if winner == side => 1,
+            Some(_) => -1,
+            None => 0
+        }
+    }
+}
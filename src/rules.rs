@@ -0,0 +1,244 @@
+use crate::bitboard::{DIAGONAL, ORTHOGONAL};
+use crate::geometry::{Geometry, SquareSet};
+
+// a variant name and the rules it stands for
+pub type Variant = (&'static str, fn() -> Rules);
+
+// every variant Rules::from_name knows, by the name it is looked up with
+pub const VARIANTS:[Variant; 10] = [
+    ("english", Rules::english),
+    ("huffing", Rules::with_huffing),
+    ("international", Rules::international),
+    ("russian", Rules::russian),
+    ("brazilian", Rules::brazilian),
+    ("canadian", Rules::canadian),
+    ("frisian", Rules::frisian),
+    ("italian", Rules::italian),
+    ("turkish", Rules::turkish),
+    ("giveaway", Rules::giveaway)
+];
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CaptureRule {
+    // a capture must be played whenever one is available
+    Mandatory,
+    // any move may be played, but a piece that could have captured and
+    // did not is removed from the board (huffed) afterwards
+    Huff
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PromotionRule {
+    // reaching the back rank crowns the man and ends the move (English)
+    EndsMove,
+    // the man is crowned as soon as it reaches the back rank and carries on
+    // capturing as a king (Russian)
+    ContinuesAsKing,
+    // the man only becomes a king if the whole sequence ends on the back
+    // rank; passing over it mid-capture does not count (International)
+    AtEndOnly
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MajorityRule {
+    // any capture sequence may be chosen (English, Russian)
+    FreeChoice,
+    // only the sequences that capture the most pieces may be played
+    // (International, Brazilian)
+    MostPieces,
+    // most pieces first; on a tie capture with a king rather than a man, then
+    // take the most kings, then take kings as early in the sequence as possible
+    Italian,
+    // the most valuable sequence, a king counting for a little less than two
+    // men; on a tie capture with a king rather than a man
+    Frisian
+}
+
+// orthogonal captures on a board of dark squares jump two columns or rows
+pub const FRISIAN_ORTHOGONAL:[(i32, i32); 4] = [(-2, 0), (0, -2), (0, 2), (2, 0)];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    pub geometry:Geometry,
+    // the steps pieces move and capture along, as (row, column) vectors seen
+    // from White, whose men move towards row 0; men never use the backward
+    // ones, except to capture when men_capture_backward is set
+    pub directions:Vec<(i32, i32)>,
+    pub capture_directions:Vec<(i32, i32)>,
+    pub capture_rule:CaptureRule,
+    pub men_capture_backward:bool,
+    pub men_capture_kings:bool,
+    pub promotion:PromotionRule,
+    // moves per side without a capture or a man move before the game is
+    // drawn; 0 turns the rule off
+    pub no_progress_moves:usize,
+    // kings move and capture any distance along a line
+    pub flying_kings:bool,
+    // captured pieces stay on the board until the sequence ends (the Turkish
+    // strike), so they block the capturing piece; otherwise each one is
+    // lifted as soon as it is jumped
+    pub remove_captured_at_end:bool,
+    pub majority:MajorityRule,
+    // giveaway: the side that loses all its pieces or has no moves left wins
+    pub losing:bool
+}
+
+impl Rules {
+
+    pub fn english() -> Rules {
+
+        Rules {
+            geometry:Geometry::square(8),
+            directions:DIAGONAL.to_vec(),
+            capture_directions:DIAGONAL.to_vec(),
+            capture_rule:CaptureRule::Mandatory,
+            men_capture_backward:false,
+            men_capture_kings:true,
+            promotion:PromotionRule::EndsMove,
+            no_progress_moves:40,
+            flying_kings:false,
+            remove_captured_at_end:true,
+            majority:MajorityRule::FreeChoice,
+            losing:false
+        }
+    }
+
+    pub fn international() -> Rules {
+
+        Rules {
+            geometry:Geometry::square(10),
+            directions:DIAGONAL.to_vec(),
+            capture_directions:DIAGONAL.to_vec(),
+            capture_rule:CaptureRule::Mandatory,
+            men_capture_backward:true,
+            men_capture_kings:true,
+            promotion:PromotionRule::AtEndOnly,
+            no_progress_moves:25,
+            flying_kings:true,
+            remove_captured_at_end:true,
+            majority:MajorityRule::MostPieces,
+            losing:false
+        }
+    }
+
+    pub fn russian() -> Rules {
+
+        Rules {
+            geometry:Geometry::square(8),
+            directions:DIAGONAL.to_vec(),
+            capture_directions:DIAGONAL.to_vec(),
+            capture_rule:CaptureRule::Mandatory,
+            men_capture_backward:true,
+            men_capture_kings:true,
+            promotion:PromotionRule::ContinuesAsKing,
+            no_progress_moves:15,
+            flying_kings:true,
+            remove_captured_at_end:true,
+            majority:MajorityRule::FreeChoice,
+            losing:false
+        }
+    }
+
+    pub fn brazilian() -> Rules {
+
+        let mut rules:Rules = Rules::international();
+
+        rules.geometry = Geometry::square(8);
+
+        return rules;
+    }
+
+    pub fn canadian() -> Rules {
+
+        let mut rules:Rules = Rules::international();
+
+        rules.geometry = Geometry::square(12);
+
+        return rules;
+    }
+
+    pub fn frisian() -> Rules {
+
+        let mut rules:Rules = Rules::international();
+
+        rules.capture_directions = [DIAGONAL, FRISIAN_ORTHOGONAL].concat();
+        rules.majority = MajorityRule::Frisian;
+
+        return rules;
+    }
+
+    pub fn italian() -> Rules {
+
+        Rules {
+            geometry:Geometry {
+                squares:SquareSet::Mirrored,
+                ..Geometry::square(8)
+            },
+            directions:DIAGONAL.to_vec(),
+            capture_directions:DIAGONAL.to_vec(),
+            capture_rule:CaptureRule::Mandatory,
+            men_capture_backward:false,
+            men_capture_kings:false,
+            promotion:PromotionRule::EndsMove,
+            no_progress_moves:40,
+            flying_kings:false,
+            remove_captured_at_end:true,
+            majority:MajorityRule::Italian,
+            losing:false
+        }
+    }
+
+    pub fn turkish() -> Rules {
+
+        Rules {
+            geometry:Geometry {
+                squares:SquareSet::All,
+                start_rows:vec![1, 2],
+                ..Geometry::square(8)
+            },
+            directions:ORTHOGONAL.to_vec(),
+            capture_directions:ORTHOGONAL.to_vec(),
+            capture_rule:CaptureRule::Mandatory,
+            men_capture_backward:false,
+            men_capture_kings:true,
+            promotion:PromotionRule::AtEndOnly,
+            no_progress_moves:40,
+            flying_kings:true,
+            remove_captured_at_end:false,
+            majority:MajorityRule::MostPieces,
+            losing:false
+        }
+    }
+
+    pub fn giveaway() -> Rules {
+
+        let mut rules:Rules = Rules::english();
+
+        rules.losing = true;
+
+        return rules;
+    }
+
+    pub fn with_huffing() -> Rules {
+
+        let mut rules:Rules = Rules::english();
+
+        rules.capture_rule = CaptureRule::Huff;
+
+        return rules;
+    }
+
+    pub fn from_name(name:&str) -> Option<Rules> {
+
+        let name:String = name.to_lowercase();
+
+        return VARIANTS.iter().find(|v| v.0 == name).map(|v| (v.1)());
+    }
+}
+
+impl Default for Rules {
+
+    fn default() -> Rules {
+        return Rules::english();
+    }
+}
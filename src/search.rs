@@ -0,0 +1,418 @@
+use std::cmp;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::board::{Board, WIN_SCORE};
+use crate::moves::Move;
+use crate::piece::Color;
+use crate::result::GameResult;
+
+// deepest iteration tried when only the clock or a stop flag limits the search
+pub const MAX_SEARCH_DEPTH:i32 = 64;
+
+// a clock is shared out as if this many moves were left to play
+pub const MOVES_TO_GO:u32 = 30;
+
+const INFINITY:i32 = 999999;
+
+// the clock and stop flag are only looked at every this many nodes
+const CHECK_INTERVAL:u64 = 256;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TimeControl {
+    // search every iteration up to this depth however long it takes
+    Depth(i32),
+    MoveTime(Duration),
+    // the time left on the clock and the time added after each move
+    Clock { remaining:Duration, increment:Duration },
+    // run until stopped
+    Infinite
+}
+
+impl TimeControl {
+
+    pub fn max_depth(&self) -> i32 {
+
+        match self {
+            TimeControl::Depth(depth) => *depth,
+            _ => MAX_SEARCH_DEPTH
+        }
+    }
+
+    // the time one move may take
+    pub fn budget(&self) -> Option<Duration> {
+
+        match self {
+            TimeControl::MoveTime(time) => Some(*time),
+            TimeControl::Clock { remaining, increment } => Some(cmp::min(*remaining / MOVES_TO_GO + *increment, *remaining / 2)),
+            _ => None
+        }
+    }
+}
+
+// The outcome of one completed iteration: the score is from the point of view
+// of the side to move, and the principal variation starts with the move to play.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchInfo {
+    pub depth:i32,
+    pub score:i32,
+    pub pv:Vec<Move>,
+    pub nodes:u64
+}
+
+// what ends a search early, and the nodes visited so far
+struct Limits<'a> {
+    stop:&'a AtomicBool,
+    deadline:Option<Instant>,
+    // the first iteration always runs to the end so there is a move to play
+    enabled:bool,
+    stopped:bool,
+    nodes:u64
+}
+
+impl Limits<'_> {
+
+    fn out_of_time(&mut self) -> bool {
+
+        if self.stopped {
+            return true;
+        }
+
+        if !self.enabled || !self.nodes.is_multiple_of(CHECK_INTERVAL) {
+            return false;
+        }
+
+        self.stopped = self.stop.load(Ordering::Relaxed) || self.deadline.is_some_and(|d| Instant::now() >= d);
+
+        return self.stopped;
+    }
+}
+
+impl Board {
+
+    // Iterative deepening alpha-beta for the side to move, one ply deeper each
+    // iteration, until the time control runs out. Every completed iteration is
+    // passed to report. Running out of time or setting stop abandons the
+    // current iteration and returns the last completed one.
+    pub fn search<F:FnMut(&SearchInfo)>(&mut self, control:&TimeControl, stop:&AtomicBool, mut report:F) -> Option<SearchInfo> {
+
+        let start:Instant = Instant::now();
+        let budget:Option<Duration> = control.budget();
+
+        let my_side:Color = self.my_side;
+        let op_side:Color = self.op_side;
+
+        // the evaluation scores positions for op_side
+        self.op_side = self.curr_player;
+        self.my_side = self.curr_player.opposite();
+
+        let mut root_moves:Vec<Move> = self.legal_moves();
+
+        let mut best:Option<SearchInfo> = None;
+
+        let mut limits:Limits = Limits {
+            stop,
+            deadline:budget.map(|b| start + b),
+            enabled:false,
+            stopped:false,
+            nodes:0
+        };
+
+        for depth in 1..=control.max_depth() {
+
+            // a stop that came during the last iteration ends the search here
+            if depth > 1 && stop.load(Ordering::Relaxed) {
+                break;
+            }
+
+            // the best move of the last iteration is searched first
+            if let Some(info) = &best {
+                if let Some(idx) = root_moves.iter().position(|m| *m == info.pv[0]) {
+                    let mv:Move = root_moves.remove(idx);
+                    root_moves.insert(0, mv);
+                }
+            }
+
+            limits.enabled = depth > 1;
+
+            let mut iteration:Option<SearchInfo> = None;
+            let mut alpha:i32 = -INFINITY;
+
+            for i in 0..root_moves.len() {
+
+                let mut line:Vec<Move> = Vec::new();
+
+                self.make_move(&root_moves[i]);
+
+                let score:Option<i32> = self.alpha_beta(depth - 1, false, alpha, INFINITY, &mut limits, &mut line);
+
+                self.unmake_move();
+
+                let score:i32 = match score {
+                    Some(_s) => _s,
+                    None => break
+                };
+
+                if iteration.is_none() || score > alpha {
+
+                    alpha = score;
+
+                    line.insert(0, root_moves[i].clone());
+
+                    iteration = Some(SearchInfo { depth, score, pv:line, nodes:0 });
+                }
+            }
+
+            if limits.stopped {
+                break;
+            }
+
+            if let Some(info) = iteration.as_mut() {
+                info.nodes = limits.nodes;
+                report(info);
+            }
+
+            best = iteration;
+
+            // nothing to think about with a single move or a won or lost game
+            if root_moves.len() <= 1 || best.as_ref().is_none_or(|b| b.score.abs() > WIN_SCORE / 2) {
+                break;
+            }
+
+            // the next iteration takes longer than all the ones before it, so
+            // it would not finish in the time left
+            if budget.is_some_and(|b| start.elapsed() * 2 > b) {
+                break;
+            }
+        }
+
+        self.my_side = my_side;
+        self.op_side = op_side;
+
+        return best;
+    }
+
+    // alpha-beta over legal_moves, scoring for op_side; None once time is up
+    fn alpha_beta(&mut self, depth:i32, is_max:bool, mut alpha:i32, mut beta:i32, limits:&mut Limits, pv:&mut Vec<Move>) -> Option<i32> {
+
+        limits.nodes += 1;
+
+        if limits.out_of_time() {
+            return None;
+        }
+
+        let result:GameResult = self.game_result();
+
+        if result.is_over() {
+            return Some(result.score_for(self.op_side) * (WIN_SCORE + depth));
+        }
+
+        if depth <= 0 {
+            return Some(self.board_heuristics());
+        }
+
+        let poss_moves:Vec<Move> = self.legal_moves();
+
+        let mut best:i32 = if is_max { -INFINITY } else { INFINITY };
+
+        for i in 0..poss_moves.len() {
+
+            let mut line:Vec<Move> = Vec::new();
+
+            self.make_move(&poss_moves[i]);
+
+            let score:Option<i32> = self.alpha_beta(depth - 1, !is_max, alpha, beta, limits, &mut line);
+
+            self.unmake_move();
+
+            let score:i32 = score?;
+
+            if (is_max && score > best) || (!is_max && score < best) {
+
+                best = score;
+
+                line.insert(0, poss_moves[i].clone());
+
+                *pv = line;
+            }
+
+            if is_max {
+                alpha = cmp::max(alpha, best);
+            } else {
+                beta = cmp::min(beta, best);
+            }
+
+            if beta <= alpha {
+                break;
+            }
+        }
+
+        return Some(best);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::rules::Rules;
+
+    fn start(rules:Rules) -> Board {
+
+        let mut board:Board = Board::with_rules(rules);
+
+        board.init();
+
+        return board;
+    }
+
+    // plain minimax without pruning, scored the way alpha_beta scores
+    fn minimax(board:&mut Board, depth:i32, is_max:bool) -> i32 {
+
+        let result:GameResult = board.game_result();
+
+        if result.is_over() {
+            return result.score_for(board.op_side) * (WIN_SCORE + depth);
+        }
+
+        if depth <= 0 {
+            return board.board_heuristics();
+        }
+
+        let moves:Vec<Move> = board.legal_moves();
+        let mut scores:Vec<i32> = Vec::new();
+
+        for i in 0..moves.len() {
+            board.make_move(&moves[i]);
+            scores.push(minimax(board, depth - 1, !is_max));
+            board.unmake_move();
+        }
+
+        if is_max {
+            return *scores.iter().max().unwrap();
+        }
+
+        return *scores.iter().min().unwrap();
+    }
+
+    fn is_legal_line(board:&mut Board, pv:&[Move]) -> bool {
+
+        let mut legal:bool = true;
+        let mut played:usize = 0;
+
+        for i in 0..pv.len() {
+
+            if !board.legal_moves().contains(&pv[i]) {
+                legal = false;
+                break;
+            }
+
+            board.make_move(&pv[i]);
+            played += 1;
+        }
+
+        for _ in 0..played {
+            board.unmake_move();
+        }
+
+        return legal;
+    }
+
+    #[test]
+    fn fixed_depth_matches_minimax() {
+
+        let cases:Vec<(Rules, i32)> = vec![(Rules::english(), 4), (Rules::international(), 3)];
+
+        for i in 0..cases.len() {
+
+            let mut board:Board = start(cases[i].0.clone());
+            let mut depths:Vec<i32> = Vec::new();
+
+            let info:SearchInfo = board.search(&TimeControl::Depth(cases[i].1), &AtomicBool::new(false), |info| depths.push(info.depth)).unwrap();
+
+            assert_eq!(depths, (1..=cases[i].1).collect::<Vec<i32>>());
+            assert_eq!(info.depth, cases[i].1);
+            assert_eq!(info.pv.len(), cases[i].1 as usize);
+            assert!(is_legal_line(&mut board, &info.pv));
+
+            // score every root move the slow way, from the side to move
+            board.op_side = board.curr_player;
+            board.my_side = board.curr_player.opposite();
+
+            let mut best:i32 = -INFINITY;
+
+            for mv in board.legal_moves() {
+
+                board.make_move(&mv);
+
+                let score:i32 = minimax(&mut board, cases[i].1 - 1, false);
+
+                board.unmake_move();
+
+                if mv == info.pv[0] {
+                    assert_eq!(score, info.score);
+                }
+
+                best = cmp::max(best, score);
+            }
+
+            assert_eq!(info.score, best);
+        }
+    }
+
+    #[test]
+    fn stop_keeps_the_first_iteration() {
+
+        let mut board:Board = start(Rules::english());
+        let fen:String = board.to_fen();
+
+        let info:SearchInfo = board.search(&TimeControl::Depth(10), &AtomicBool::new(true), |_| {}).unwrap();
+
+        assert_eq!(info.depth, 1);
+        assert!(board.legal_moves().contains(&info.pv[0]));
+
+        // the board is left as it was
+        assert_eq!(board.to_fen(), fen);
+        assert_eq!(board.op_side, Color::Black);
+    }
+
+    #[test]
+    fn move_time_returns_the_last_completed_iteration() {
+
+        let mut board:Board = start(Rules::international());
+        let mut reported:Vec<SearchInfo> = Vec::new();
+
+        let info:SearchInfo = board.search(&TimeControl::MoveTime(Duration::from_micros(1)), &AtomicBool::new(false), |info| reported.push(info.clone())).unwrap();
+
+        assert_eq!(info.depth, 1);
+        assert_eq!(reported, vec![info.clone()]);
+        assert!(board.legal_moves().contains(&info.pv[0]));
+
+        let info:SearchInfo = board.search(&TimeControl::MoveTime(Duration::from_millis(200)), &AtomicBool::new(false), |_| {}).unwrap();
+
+        assert!(info.depth > 1);
+        assert!(is_legal_line(&mut board, &info.pv));
+    }
+
+    #[test]
+    fn budgets_and_depths() {
+
+        let clock:TimeControl = TimeControl::Clock { remaining:Duration::from_secs(60), increment:Duration::from_secs(1) };
+
+        // a thirtieth of the clock plus the increment
+        assert_eq!(clock.budget(), Some(Duration::from_secs(3)));
+
+        // but never more than half of what is left
+        let short:TimeControl = TimeControl::Clock { remaining:Duration::from_secs(2), increment:Duration::from_secs(5) };
+
+        assert_eq!(short.budget(), Some(Duration::from_secs(1)));
+
+        assert_eq!(TimeControl::MoveTime(Duration::from_millis(250)).budget(), Some(Duration::from_millis(250)));
+        assert_eq!(TimeControl::Depth(5).budget(), None);
+        assert_eq!(TimeControl::Infinite.budget(), None);
+
+        assert_eq!(TimeControl::Depth(5).max_depth(), 5);
+        assert_eq!(clock.max_depth(), MAX_SEARCH_DEPTH);
+        assert_eq!(TimeControl::Infinite.max_depth(), MAX_SEARCH_DEPTH);
+    }
+}
@@ -36,6 +36,11 @@ def initData():
 
     for data in allData:
 
+        # only learn from moves of games the engine went on to win; older
+        # data files have no result and only held won games
+        if data.get("result", [1])[0] < 1:
+            continue
+
         boardStates.append(game.normalizeBoard(data["board"]))
 
         for i in range(len(data["moves"])):